@@ -0,0 +1,292 @@
+use crate::ResourceData::ResourceData;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+/// Highest ammo SDU level, the number of SDUs bought for a pool in `BlackMarketUpgrades`.
+pub const MAX_AMMO_LEVEL: i32 = 7;
+
+#[derive(Debug)]
+pub enum AmmoError {
+    InvalidLevel { msg: String },
+}
+
+impl std::fmt::Display for AmmoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AmmoError::InvalidLevel { msg } => write!(f, "InvalidLevel: {}", msg),
+        }
+    }
+}
+
+///
+/// Ammo pools stored in `ResourceData`.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AmmoType {
+    AssaultRifle,
+    Pistol,
+    SMG,
+    Shotgun,
+    Sniper,
+    Launcher,
+    Grenade,
+}
+
+impl AmmoType {
+    pub const ALL: [AmmoType; 7] = [
+        AmmoType::AssaultRifle,
+        AmmoType::Pistol,
+        AmmoType::SMG,
+        AmmoType::Shotgun,
+        AmmoType::Sniper,
+        AmmoType::Launcher,
+        AmmoType::Grenade,
+    ];
+
+    ///
+    /// Returns the path of the resource, `ResourceData.Resource`.
+    ///
+    pub fn resource(self) -> &'static str {
+        match self {
+            AmmoType::AssaultRifle => "D_Resources.AmmoResources.Ammo_Combat_Rifle",
+            AmmoType::Pistol => "D_Resources.AmmoResources.Ammo_Repeater_Pistol",
+            AmmoType::SMG => "D_Resources.AmmoResources.Ammo_Patrol_SMG",
+            AmmoType::Shotgun => "D_Resources.AmmoResources.Ammo_Combat_Shotgun",
+            AmmoType::Sniper => "D_Resources.AmmoResources.Ammo_Sniper_Rifle",
+            AmmoType::Launcher => "D_Resources.AmmoResources.Ammo_Rocket_Launcher",
+            AmmoType::Grenade => "D_Resources.AmmoResources.Ammo_Grenade_Protean",
+        }
+    }
+
+    ///
+    /// Returns the path of the resource pool, `ResourceData.Pool`.
+    ///
+    pub fn pool(self) -> &'static str {
+        match self {
+            AmmoType::AssaultRifle => "D_Resourcepools.AmmoPools.Ammo_Combat_Rifle_Pool",
+            AmmoType::Pistol => "D_Resourcepools.AmmoPools.Ammo_Repeater_Pistol_Pool",
+            AmmoType::SMG => "D_Resourcepools.AmmoPools.Ammo_Patrol_SMG_Pool",
+            AmmoType::Shotgun => "D_Resourcepools.AmmoPools.Ammo_Combat_Shotgun_Pool",
+            AmmoType::Sniper => "D_Resourcepools.AmmoPools.Ammo_Sniper_Rifle_Pool",
+            AmmoType::Launcher => "D_Resourcepools.AmmoPools.Ammo_Rocket_Launcher_Pool",
+            AmmoType::Grenade => "D_Resourcepools.AmmoPools.Ammo_Grenade_Protean_Pool",
+        }
+    }
+
+    ///
+    /// Returns the position of the SDU of the pool in `BlackMarketUpgrades`.
+    ///
+    /// Save0001 bought every ammo SDU up to the same level, so it only confirms that the
+    /// first seven entries are the ammo SDUs. The order between the ammo types is not
+    /// verified against a save with differing levels.
+    ///
+    pub fn upgrade_index(self) -> usize {
+        match self {
+            AmmoType::AssaultRifle => 0,
+            AmmoType::Pistol => 1,
+            AmmoType::Launcher => 2,
+            AmmoType::Shotgun => 3,
+            AmmoType::SMG => 4,
+            AmmoType::Sniper => 5,
+            AmmoType::Grenade => 6,
+        }
+    }
+
+    ///
+    /// Returns the capacity of the pool for every SDU level from 0 to `MAX_AMMO_LEVEL`.
+    ///
+    /// Level 0 is the capacity without any SDU. Save0001 has bought all seven SDUs of every
+    /// pool and holds 1260 assault rifle and 900 pistol ammo.
+    ///
+    pub fn capacities(self) -> &'static [i32] {
+        match self {
+            AmmoType::AssaultRifle => &[280, 420, 560, 700, 840, 980, 1120, 1260],
+            AmmoType::Pistol => &[200, 300, 400, 500, 600, 700, 800, 900],
+            AmmoType::SMG => &[360, 540, 720, 900, 1080, 1260, 1440, 1620],
+            AmmoType::Shotgun => &[80, 120, 160, 200, 240, 280, 320, 360],
+            AmmoType::Sniper => &[48, 60, 72, 84, 96, 108, 120, 132],
+            AmmoType::Launcher => &[12, 15, 18, 21, 24, 27, 30, 33],
+            AmmoType::Grenade => &[3, 4, 5, 6, 7, 8, 9, 10],
+        }
+    }
+
+    ///
+    /// Returns the capacity of the pool at an SDU level, clamped to the valid levels.
+    ///
+    pub fn capacity(self, level: i32) -> i32 {
+        self.capacities()[level.clamp(0, MAX_AMMO_LEVEL) as usize]
+    }
+}
+
+///
+/// Returns the `ResourceData.UpgradeLevel` stored for an SDU level.
+///
+/// Save0001 stores `UpgradeLevel` 6 for pools with all seven SDUs, the capacity follows the
+/// SDU level in `BlackMarketUpgrades`.
+///
+fn resource_level(level: i32) -> i32 {
+    (level - 1).max(0)
+}
+
+fn resource(save: &WillowTwoPlayerSaveGame, ammo: AmmoType) -> Option<&ResourceData> {
+    save.ResourceData
+        .iter()
+        .find(|resource| resource.Resource() == ammo.resource())
+}
+
+fn resource_mut(save: &mut WillowTwoPlayerSaveGame, ammo: AmmoType) -> &mut ResourceData {
+    let level = resource_level(ammo_level(save, ammo));
+    let index = match save
+        .ResourceData
+        .iter()
+        .position(|resource| resource.Resource() == ammo.resource())
+    {
+        Some(index) => index,
+        None => {
+            let mut resource = ResourceData::new();
+            resource.set_Resource(ammo.resource().to_string());
+            resource.set_Pool(ammo.pool().to_string());
+            resource.set_Amount(0.0);
+            resource.set_UpgradeLevel(level);
+            save.ResourceData.push(resource);
+            save.ResourceData.len() - 1
+        }
+    };
+    &mut save.ResourceData[index]
+}
+
+///
+/// Returns the SDU level of an ammo pool as stored in `BlackMarketUpgrades`, missing entries
+/// count as 0.
+///
+pub fn ammo_level(save: &WillowTwoPlayerSaveGame, ammo: AmmoType) -> i32 {
+    save.BlackMarketUpgrades
+        .get(ammo.upgrade_index())
+        .copied()
+        .unwrap_or(0)
+}
+
+///
+/// Returns the ammo left in a pool.
+///
+pub fn ammo_amount(save: &WillowTwoPlayerSaveGame, ammo: AmmoType) -> i32 {
+    resource(save, ammo)
+        .map(|resource| resource.Amount() as i32)
+        .unwrap_or(0)
+}
+
+///
+/// Returns the capacity of an ammo pool at its current SDU level.
+///
+pub fn ammo_capacity(save: &WillowTwoPlayerSaveGame, ammo: AmmoType) -> i32 {
+    ammo.capacity(ammo_level(save, ammo))
+}
+
+///
+/// Sets the SDU level of an ammo pool in `BlackMarketUpgrades`.
+///
+/// The `ResourceData` of the pool is created if needed and its `UpgradeLevel` updated. Ammo
+/// beyond the capacity of a lowered level is dropped.
+///
+pub fn set_ammo_level(
+    save: &mut WillowTwoPlayerSaveGame,
+    ammo: AmmoType,
+    level: i32,
+) -> Result<(), AmmoError> {
+    if !(0..=MAX_AMMO_LEVEL).contains(&level) {
+        return Err(AmmoError::InvalidLevel {
+            msg: format!(
+                "The ammo SDU level {} is outside of 0 to {}!",
+                level, MAX_AMMO_LEVEL
+            ),
+        });
+    }
+    let index = ammo.upgrade_index();
+    if save.BlackMarketUpgrades.len() <= index {
+        save.BlackMarketUpgrades.resize(index + 1, 0);
+    }
+    save.BlackMarketUpgrades[index] = level;
+
+    let resource = resource_mut(save, ammo);
+    resource.set_UpgradeLevel(resource_level(level));
+    let capacity = ammo.capacity(level) as f32;
+    if resource.Amount() > capacity {
+        resource.set_Amount(capacity);
+    }
+    Ok(())
+}
+
+///
+/// Fills an ammo pool to the capacity of its SDU level and returns the new amount.
+///
+pub fn refill_ammo(save: &mut WillowTwoPlayerSaveGame, ammo: AmmoType) -> i32 {
+    let capacity = ammo_capacity(save, ammo);
+    resource_mut(save, ammo).set_Amount(capacity as f32);
+    capacity
+}
+
+///
+/// Fills all ammo pools, see `refill_ammo`.
+///
+pub fn refill_all_ammo(save: &mut WillowTwoPlayerSaveGame) {
+    for ammo in AmmoType::ALL {
+        refill_ammo(save, ammo);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ammo_amount, ammo_capacity, ammo_level, refill_all_ammo, refill_ammo, resource,
+        set_ammo_level, AmmoType,
+    };
+    use crate::load_test_save;
+
+    #[test]
+    fn ammo_level_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(ammo_level(&save_game, AmmoType::Sniper), 7);
+        assert_eq!(ammo_capacity(&save_game, AmmoType::Sniper), 132);
+        assert_eq!(ammo_amount(&save_game, AmmoType::Sniper), 68);
+        let index = AmmoType::Sniper.upgrade_index();
+        let original = save_game.clone();
+        set_ammo_level(&mut save_game, AmmoType::Sniper, 7).unwrap();
+        assert_eq!(save_game, original);
+
+        assert_eq!(refill_ammo(&mut save_game, AmmoType::Sniper), 132);
+        set_ammo_level(&mut save_game, AmmoType::Sniper, 1).unwrap();
+        assert_eq!(ammo_amount(&save_game, AmmoType::Sniper), 60);
+        assert_eq!(save_game.BlackMarketUpgrades[index], 1);
+        set_ammo_level(&mut save_game, AmmoType::Sniper, 0).unwrap();
+        assert_eq!(ammo_amount(&save_game, AmmoType::Sniper), 48);
+        assert_eq!(save_game.BlackMarketUpgrades[index], 0);
+        let sniper = resource(&save_game, AmmoType::Sniper).unwrap();
+        assert_eq!(sniper.UpgradeLevel(), 0);
+        assert!(set_ammo_level(&mut save_game, AmmoType::Sniper, 8).is_err());
+    }
+
+    #[test]
+    fn refill_all_ammo_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(ammo_amount(&save_game, AmmoType::AssaultRifle), 1260);
+        assert_eq!(ammo_amount(&save_game, AmmoType::Pistol), 900);
+        refill_all_ammo(&mut save_game);
+        assert_eq!(ammo_amount(&save_game, AmmoType::AssaultRifle), 1260);
+        assert_eq!(ammo_amount(&save_game, AmmoType::Pistol), 900);
+        assert_eq!(ammo_amount(&save_game, AmmoType::Launcher), 33);
+
+        save_game
+            .ResourceData
+            .retain(|resource| resource.Resource() != AmmoType::Grenade.resource());
+        refill_all_ammo(&mut save_game);
+        assert_eq!(save_game.ResourceData.len(), 7);
+        assert_eq!(ammo_amount(&save_game, AmmoType::Grenade), 10);
+        let grenade = resource(&save_game, AmmoType::Grenade).unwrap();
+        assert_eq!(grenade.UpgradeLevel(), 6);
+
+        save_game.BlackMarketUpgrades.clear();
+        refill_all_ammo(&mut save_game);
+        assert_eq!(ammo_level(&save_game, AmmoType::Grenade), 0);
+        assert_eq!(ammo_amount(&save_game, AmmoType::Grenade), 3);
+        assert_eq!(ammo_amount(&save_game, AmmoType::AssaultRifle), 280);
+    }
+}
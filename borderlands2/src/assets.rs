@@ -0,0 +1,1190 @@
+///
+/// Kinds of assets referenced from inventory serials and balance definitions.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AssetKind {
+    WeaponType,
+    ItemType,
+    WeaponBalance,
+    ItemBalance,
+    Manufacturer,
+    WeaponPart,
+    ItemPart,
+}
+
+impl AssetKind {
+    ///
+    /// Returns the number of bits used for the sublibrary index (including the set flag) and
+    /// for the asset index when a reference of this kind is packed into a serial.
+    ///
+    pub fn bit_sizes(self) -> (u32, u32) {
+        match self {
+            AssetKind::WeaponType => (7, 6),
+            AssetKind::ItemType => (9, 8),
+            AssetKind::WeaponBalance => (10, 10),
+            AssetKind::ItemBalance => (10, 10),
+            AssetKind::Manufacturer => (4, 7),
+            AssetKind::WeaponPart => (6, 11),
+            AssetKind::ItemPart => (6, 10),
+        }
+    }
+}
+
+///
+/// Part slots of weapons and items.
+///
+/// Weapons use the slots from `Body` to `Accessory2`, items use `Alpha` to `Theta`.
+/// `Material`, `Prefix` and `Title` are shared by both.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PartSlot {
+    Body,
+    Grip,
+    Barrel,
+    Sight,
+    Stock,
+    Elemental,
+    Accessory1,
+    Accessory2,
+    Alpha,
+    Beta,
+    Gamma,
+    Delta,
+    Epsilon,
+    Zeta,
+    Eta,
+    Theta,
+    Material,
+    Prefix,
+    Title,
+}
+
+impl PartSlot {
+    ///
+    /// Weapon part slots in the order they are stored in a serial.
+    ///
+    pub const WEAPON: [PartSlot; 11] = [
+        PartSlot::Body,
+        PartSlot::Grip,
+        PartSlot::Barrel,
+        PartSlot::Sight,
+        PartSlot::Stock,
+        PartSlot::Elemental,
+        PartSlot::Accessory1,
+        PartSlot::Accessory2,
+        PartSlot::Material,
+        PartSlot::Prefix,
+        PartSlot::Title,
+    ];
+
+    ///
+    /// Item part slots in the order they are stored in a serial.
+    ///
+    pub const ITEM: [PartSlot; 11] = [
+        PartSlot::Alpha,
+        PartSlot::Beta,
+        PartSlot::Gamma,
+        PartSlot::Delta,
+        PartSlot::Epsilon,
+        PartSlot::Zeta,
+        PartSlot::Eta,
+        PartSlot::Theta,
+        PartSlot::Material,
+        PartSlot::Prefix,
+        PartSlot::Title,
+    ];
+}
+
+///
+/// A package of assets. The position of an asset in `assets` is its index in a serial.
+///
+#[derive(Debug)]
+pub struct AssetSublibrary {
+    pub package: &'static str,
+    pub assets: &'static [&'static str],
+}
+
+///
+/// The asset libraries of the base game (set 0) or of a DLC.
+///
+#[derive(Debug)]
+pub struct AssetSet {
+    pub id: u8,
+    pub name: &'static str,
+    pub weapon_types: &'static [AssetSublibrary],
+    pub item_types: &'static [AssetSublibrary],
+    pub weapon_balances: &'static [AssetSublibrary],
+    pub item_balances: &'static [AssetSublibrary],
+    pub manufacturers: &'static [AssetSublibrary],
+    pub weapon_parts: &'static [AssetSublibrary],
+    pub item_parts: &'static [AssetSublibrary],
+}
+
+impl AssetSet {
+    ///
+    /// Returns the sublibraries of the given kind.
+    ///
+    pub fn sublibraries(&self, kind: AssetKind) -> &'static [AssetSublibrary] {
+        match kind {
+            AssetKind::WeaponType => self.weapon_types,
+            AssetKind::ItemType => self.item_types,
+            AssetKind::WeaponBalance => self.weapon_balances,
+            AssetKind::ItemBalance => self.item_balances,
+            AssetKind::Manufacturer => self.manufacturers,
+            AssetKind::WeaponPart => self.weapon_parts,
+            AssetKind::ItemPart => self.item_parts,
+        }
+    }
+}
+
+///
+/// Reference to an asset as it is stored in a serial.
+///
+/// If `use_set` is set the asset is looked up in the set of the serial, otherwise in the
+/// base game set.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AssetReference {
+    pub use_set: bool,
+    pub sublibrary: u32,
+    pub asset: u32,
+}
+
+///
+/// Returns the asset set with the given id.
+///
+pub fn asset_set(id: u8) -> Option<&'static AssetSet> {
+    ASSET_SETS.iter().find(|set| set.id == id)
+}
+
+///
+/// Resolves a reference from a serial with the given set id to the full asset path.
+///
+pub fn asset_name(set_id: u8, kind: AssetKind, reference: &AssetReference) -> Option<String> {
+    let set = asset_set(if reference.use_set { set_id } else { 0 })?;
+    let sublibrary = set.sublibraries(kind).get(reference.sublibrary as usize)?;
+    let asset = sublibrary.assets.get(reference.asset as usize)?;
+    Some(format!("{}.{}", sublibrary.package, asset))
+}
+
+///
+/// Finds the set id and the reference of an asset given by its full path.
+///
+pub fn find_asset(kind: AssetKind, name: &str) -> Option<(u8, AssetReference)> {
+    let (package, asset_name) = name.rsplit_once('.')?;
+    for set in ASSET_SETS {
+        for (sublibrary_index, sublibrary) in set.sublibraries(kind).iter().enumerate() {
+            if sublibrary.package != package {
+                continue;
+            }
+            if let Some(asset_index) = sublibrary.assets.iter().position(|a| *a == asset_name) {
+                return Some((
+                    set.id,
+                    AssetReference {
+                        use_set: set.id != 0,
+                        sublibrary: sublibrary_index as u32,
+                        asset: asset_index as u32,
+                    },
+                ));
+            }
+        }
+    }
+    None
+}
+
+///
+/// Categories of items. Only one item of every category can be equipped.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ItemCategory {
+    Shield,
+    GrenadeMod,
+    ClassMod,
+    Relic,
+}
+
+///
+/// Returns the category of an item from the full path of its type.
+///
+pub fn item_category(item_type: &str) -> Option<ItemCategory> {
+    let package = item_type.split('.').next()?;
+    match package {
+        "GD_Shields" => Some(ItemCategory::Shield),
+        "GD_GrenadeMods" => Some(ItemCategory::GrenadeMod),
+        "GD_ClassMods" => Some(ItemCategory::ClassMod),
+        "GD_Artifacts" => Some(ItemCategory::Relic),
+        _ => None,
+    }
+}
+
+///
+/// Definition of a weapon or item balance: the type and manufacturers it can roll with
+/// and the valid parts for every part slot.
+///
+#[derive(Debug)]
+pub struct BalanceDefinition {
+    pub name: &'static str,
+    pub is_weapon: bool,
+    pub item_type: &'static str,
+    pub manufacturers: &'static [&'static str],
+    pub parts: &'static [(PartSlot, &'static [&'static str])],
+}
+
+impl BalanceDefinition {
+    ///
+    /// Returns the valid parts for a slot. Slots the balance does not use have no choices.
+    ///
+    pub fn choices(&self, slot: PartSlot) -> &'static [&'static str] {
+        self.parts
+            .iter()
+            .find(|(s, _)| *s == slot)
+            .map(|(_, parts)| *parts)
+            .unwrap_or(&[])
+    }
+}
+
+///
+/// Returns the balance definition with the given full path.
+///
+pub fn balance(name: &str) -> Option<&'static BalanceDefinition> {
+    BALANCES.iter().find(|balance| balance.name == name)
+}
+
+///
+/// Returns all known balance definitions.
+///
+pub fn balances() -> &'static [BalanceDefinition] {
+    BALANCES
+}
+
+///
+/// A small sample of assets, not the game's asset library. The game keeps all assets of a
+/// package in one sublibrary, so the indices here do not match real serials and gear built
+/// from them only resolves within this crate.
+///
+static ASSET_SETS: &[AssetSet] = &[AssetSet {
+    id: 0,
+    name: "Base Game",
+    weapon_types: &[
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.A_Weapons",
+            assets: &["WT_Jakobs_Pistol", "WT_Maliwan_Pistol", "WT_Vladof_Pistol"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.A_Weapons",
+            assets: &["WT_Dahl_AssaultRifle", "WT_Vladof_AssaultRifle"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.A_Weapons",
+            assets: &["WT_Hyperion_Sniper", "WT_Jakobs_Sniper"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.A_Weapons",
+            assets: &["WT_Torgue_Shotgun"],
+        },
+    ],
+    item_types: &[
+        AssetSublibrary {
+            package: "GD_Shields.A_Item",
+            assets: &["Shield_Standard", "Shield_Nova"],
+        },
+        AssetSublibrary {
+            package: "GD_GrenadeMods.A_Item",
+            assets: &["GM_Standard", "GM_Singularity"],
+        },
+        AssetSublibrary {
+            package: "GD_ClassMods.A_Item_Assassin",
+            assets: &["ClassMod_Assassin_Sniper"],
+        },
+        AssetSublibrary {
+            package: "GD_Artifacts.A_Item",
+            assets: &["A_Elemental"],
+        },
+    ],
+    weapon_balances: &[
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.A_Weapons",
+            assets: &["Pistol_Jakobs_3_Rare", "Pistol_Vladof_3_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.A_Weapons_Legendary",
+            assets: &["Pistol_Jakobs_5_Maggie"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.A_Weapons",
+            assets: &["AR_Vladof_3_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.A_Weapons",
+            assets: &["Sniper_Hyperion_3_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.A_Weapons",
+            assets: &["SG_Torgue_3_Rare"],
+        },
+    ],
+    item_balances: &[
+        AssetSublibrary {
+            package: "GD_ItemGrades.Shields",
+            assets: &["ItemGrade_Gear_Shield_Standard_03_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_ItemGrades.GrenadeMods",
+            assets: &["ItemGrade_GrenadeMod_Standard_03_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_ItemGrades.ClassMods",
+            assets: &["BalDef_ClassMod_Assassin_03_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Artifacts.A_Item",
+            assets: &["A_Elemental_Status_Rare"],
+        },
+    ],
+    manufacturers: &[AssetSublibrary {
+        package: "GD_Manufacturers.Manufacturers",
+        assets: &[
+            "Bandit", "Dahl", "Hyperion", "Jakobs", "Maliwan", "Tediore", "Torgue", "Vladof",
+            "Anshin", "Pangolin", "Eridian",
+        ],
+    }],
+    weapon_parts: &[
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.Body",
+            assets: &["Pistol_Body_Jakobs", "Pistol_Body_Vladof"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.Grip",
+            assets: &[
+                "Pistol_Grip_Jakobs",
+                "Pistol_Grip_Vladof",
+                "Pistol_Grip_Maliwan",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.Barrel",
+            assets: &[
+                "Pistol_Barrel_Jakobs",
+                "Pistol_Barrel_Vladof",
+                "Pistol_Barrel_Jakobs_Maggie",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.Sight",
+            assets: &[
+                "Pistol_Sight_Jakobs",
+                "Pistol_Sight_Vladof",
+                "Pistol_Sight_Dahl",
+                "Pistol_Sight_None",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.elemental",
+            assets: &[
+                "Pistol_Elemental_None",
+                "Pistol_Elemental_Fire",
+                "Pistol_Elemental_Shock",
+                "Pistol_Elemental_Corrosive",
+                "Pistol_Elemental_Slag",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.Accessory",
+            assets: &[
+                "Pistol_Accessory_None",
+                "Pistol_Accessory_Bayonet",
+                "Pistol_Accessory_Body1_Accurate",
+                "Pistol_Accessory_Body2_Damage",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.ManufacturerMaterials",
+            assets: &[
+                "Material_Jakobs_3",
+                "Material_Vladof_3",
+                "Material_Jakobs_5_Maggie",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.Prefix",
+            assets: &["Prefix_Jakobs_Rare", "Prefix_Vladof_Rare", "Prefix_Maggie"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Pistol.Title",
+            assets: &["Title_Jakobs_Rare", "Title_Vladof_Rare", "Title_Maggie"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.Body",
+            assets: &["AR_Body_Vladof", "AR_Body_Dahl"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.Grip",
+            assets: &["AR_Grip_Vladof", "AR_Grip_Dahl", "AR_Grip_Torgue"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.Barrel",
+            assets: &["AR_Barrel_Vladof", "AR_Barrel_Dahl", "AR_Barrel_Jakobs"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.Sight",
+            assets: &["AR_Sight_Vladof", "AR_Sight_Dahl", "AR_Sight_None"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.Stock",
+            assets: &["AR_Stock_Vladof", "AR_Stock_Dahl", "AR_Stock_Jakobs"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.elemental",
+            assets: &[
+                "AR_Elemental_None",
+                "AR_Elemental_Fire",
+                "AR_Elemental_Shock",
+                "AR_Elemental_Corrosive",
+                "AR_Elemental_Slag",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.Accessory",
+            assets: &[
+                "AR_Accessory_None",
+                "AR_Accessory_Bayonet",
+                "AR_Accessory_Body1_Accurate",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.ManufacturerMaterials",
+            assets: &["Material_Vladof_3"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.Prefix",
+            assets: &["Prefix_Vladof_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_AssaultRifle.Title",
+            assets: &["Title_Vladof_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.Body",
+            assets: &["SR_Body_Hyperion"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.Grip",
+            assets: &["SR_Grip_Hyperion", "SR_Grip_Jakobs", "SR_Grip_Maliwan"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.Barrel",
+            assets: &["SR_Barrel_Hyperion", "SR_Barrel_Jakobs", "SR_Barrel_Vladof"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.Sight",
+            assets: &["SR_Sight_Hyperion", "SR_Sight_Jakobs", "SR_Sight_Maliwan"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.Stock",
+            assets: &["SR_Stock_Hyperion", "SR_Stock_Jakobs", "SR_Stock_Vladof"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.elemental",
+            assets: &[
+                "Sniper_Elemental_None",
+                "Sniper_Elemental_Fire",
+                "Sniper_Elemental_Shock",
+                "Sniper_Elemental_Corrosive",
+                "Sniper_Elemental_Slag",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.Accessory",
+            assets: &["SR_Accessory_None", "SR_Accessory_Body1_Accurate"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.ManufacturerMaterials",
+            assets: &["Material_Hyperion_3"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.Prefix",
+            assets: &["Prefix_Hyperion_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_SniperRifles.Title",
+            assets: &["Title_Hyperion_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.Body",
+            assets: &["SG_Body_Torgue"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.Grip",
+            assets: &["SG_Grip_Torgue", "SG_Grip_Bandit", "SG_Grip_Jakobs"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.Barrel",
+            assets: &["SG_Barrel_Torgue", "SG_Barrel_Bandit", "SG_Barrel_Jakobs"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.Sight",
+            assets: &["SG_Sight_Torgue", "SG_Sight_None"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.Stock",
+            assets: &["SG_Stock_Torgue", "SG_Stock_Bandit"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.elemental",
+            assets: &[
+                "SG_Elemental_None",
+                "SG_Elemental_Fire",
+                "SG_Elemental_Corrosive",
+                "SG_Elemental_Slag",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.Accessory",
+            assets: &["SG_Accessory_None", "SG_Accessory_Body1_Accurate"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.ManufacturerMaterials",
+            assets: &["Material_Torgue_3"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.Prefix",
+            assets: &["Prefix_Torgue_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_Weap_Shotgun.Title",
+            assets: &["Title_Torgue_Rare"],
+        },
+    ],
+    item_parts: &[
+        AssetSublibrary {
+            package: "GD_Shields.Body",
+            assets: &["Body_Anshin_Standard", "Body_Pangolin_Standard"],
+        },
+        AssetSublibrary {
+            package: "GD_Shields.Battery",
+            assets: &["Battery_Anshin", "Battery_Pangolin"],
+        },
+        AssetSublibrary {
+            package: "GD_Shields.Capacitor",
+            assets: &["Capacitor_Anshin", "Capacitor_Pangolin"],
+        },
+        AssetSublibrary {
+            package: "GD_Shields.Accessory",
+            assets: &["Accessory_None", "Accessory_Capacity", "Accessory_Recharge"],
+        },
+        AssetSublibrary {
+            package: "GD_Shields.ManufacturerMaterials",
+            assets: &["Material_Anshin_3", "Material_Pangolin_3"],
+        },
+        AssetSublibrary {
+            package: "GD_Shields.Title",
+            assets: &["Title_Standard_Rare"],
+        },
+        AssetSublibrary {
+            package: "GD_GrenadeMods.Payload",
+            assets: &["Payload_Standard", "Payload_Singularity"],
+        },
+        AssetSublibrary {
+            package: "GD_GrenadeMods.Delivery",
+            assets: &["Delivery_Standard", "Delivery_LongBow", "Delivery_Mirv"],
+        },
+        AssetSublibrary {
+            package: "GD_GrenadeMods.Trigger",
+            assets: &["Trigger_Standard", "Trigger_Sticky"],
+        },
+        AssetSublibrary {
+            package: "GD_GrenadeMods.Damage",
+            assets: &["Damage_Grade1", "Damage_Grade2", "Damage_Grade3"],
+        },
+        AssetSublibrary {
+            package: "GD_GrenadeMods.Element",
+            assets: &[
+                "Element_None",
+                "Element_Fire",
+                "Element_Shock",
+                "Element_Slag",
+            ],
+        },
+        AssetSublibrary {
+            package: "GD_GrenadeMods.ManufacturerMaterials",
+            assets: &["Material_Bandit_3", "Material_Dahl_3"],
+        },
+        AssetSublibrary {
+            package: "GD_GrenadeMods.Title",
+            assets: &["Title_Standard", "Title_Singularity"],
+        },
+        AssetSublibrary {
+            package: "GD_ClassMods.Specialization",
+            assets: &["Spec_Sniper", "Spec_Killer"],
+        },
+        AssetSublibrary {
+            package: "GD_ClassMods.StatPrimary",
+            assets: &["Stat_CritDamage", "Stat_GunDamage"],
+        },
+        AssetSublibrary {
+            package: "GD_ClassMods.StatSecondary",
+            assets: &["Stat_SniperDamage", "Stat_ReloadSpeed"],
+        },
+        AssetSublibrary {
+            package: "GD_ClassMods.Title",
+            assets: &["Title_Sniper", "Title_Killer"],
+        },
+        AssetSublibrary {
+            package: "GD_Artifacts.Body",
+            assets: &["Body_Fire", "Body_Shock", "Body_Corrosive"],
+        },
+        AssetSublibrary {
+            package: "GD_Artifacts.Upgrade",
+            assets: &["Upgrade_Chance", "Upgrade_Damage"],
+        },
+        AssetSublibrary {
+            package: "GD_Artifacts.Title",
+            assets: &["Title_Elemental"],
+        },
+    ],
+}];
+
+static BALANCES: &[BalanceDefinition] = &[
+    BalanceDefinition {
+        name: "GD_Weap_Pistol.A_Weapons.Pistol_Jakobs_3_Rare",
+        is_weapon: true,
+        item_type: "GD_Weap_Pistol.A_Weapons.WT_Jakobs_Pistol",
+        manufacturers: &["GD_Manufacturers.Manufacturers.Jakobs"],
+        parts: &[
+            (PartSlot::Body, &["GD_Weap_Pistol.Body.Pistol_Body_Jakobs"]),
+            (
+                PartSlot::Grip,
+                &[
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Jakobs",
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Vladof",
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Maliwan",
+                ],
+            ),
+            (
+                PartSlot::Barrel,
+                &[
+                    "GD_Weap_Pistol.Barrel.Pistol_Barrel_Jakobs",
+                    "GD_Weap_Pistol.Barrel.Pistol_Barrel_Vladof",
+                ],
+            ),
+            (
+                PartSlot::Sight,
+                &[
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_Jakobs",
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_Dahl",
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_None",
+                ],
+            ),
+            (
+                PartSlot::Elemental,
+                &["GD_Weap_Pistol.elemental.Pistol_Elemental_None"],
+            ),
+            (
+                PartSlot::Accessory1,
+                &[
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_None",
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_Bayonet",
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_Body1_Accurate",
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_Body2_Damage",
+                ],
+            ),
+            (
+                PartSlot::Material,
+                &["GD_Weap_Pistol.ManufacturerMaterials.Material_Jakobs_3"],
+            ),
+            (
+                PartSlot::Prefix,
+                &["GD_Weap_Pistol.Prefix.Prefix_Jakobs_Rare"],
+            ),
+            (PartSlot::Title, &["GD_Weap_Pistol.Title.Title_Jakobs_Rare"]),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_Weap_Pistol.A_Weapons.Pistol_Vladof_3_Rare",
+        is_weapon: true,
+        item_type: "GD_Weap_Pistol.A_Weapons.WT_Vladof_Pistol",
+        manufacturers: &["GD_Manufacturers.Manufacturers.Vladof"],
+        parts: &[
+            (PartSlot::Body, &["GD_Weap_Pistol.Body.Pistol_Body_Vladof"]),
+            (
+                PartSlot::Grip,
+                &[
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Vladof",
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Jakobs",
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Maliwan",
+                ],
+            ),
+            (
+                PartSlot::Barrel,
+                &[
+                    "GD_Weap_Pistol.Barrel.Pistol_Barrel_Vladof",
+                    "GD_Weap_Pistol.Barrel.Pistol_Barrel_Jakobs",
+                ],
+            ),
+            (
+                PartSlot::Sight,
+                &[
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_Vladof",
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_Dahl",
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_None",
+                ],
+            ),
+            (
+                PartSlot::Elemental,
+                &[
+                    "GD_Weap_Pistol.elemental.Pistol_Elemental_None",
+                    "GD_Weap_Pistol.elemental.Pistol_Elemental_Fire",
+                    "GD_Weap_Pistol.elemental.Pistol_Elemental_Shock",
+                    "GD_Weap_Pistol.elemental.Pistol_Elemental_Corrosive",
+                    "GD_Weap_Pistol.elemental.Pistol_Elemental_Slag",
+                ],
+            ),
+            (
+                PartSlot::Accessory1,
+                &[
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_None",
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_Bayonet",
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_Body1_Accurate",
+                ],
+            ),
+            (
+                PartSlot::Material,
+                &["GD_Weap_Pistol.ManufacturerMaterials.Material_Vladof_3"],
+            ),
+            (
+                PartSlot::Prefix,
+                &["GD_Weap_Pistol.Prefix.Prefix_Vladof_Rare"],
+            ),
+            (PartSlot::Title, &["GD_Weap_Pistol.Title.Title_Vladof_Rare"]),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_Weap_Pistol.A_Weapons_Legendary.Pistol_Jakobs_5_Maggie",
+        is_weapon: true,
+        item_type: "GD_Weap_Pistol.A_Weapons.WT_Jakobs_Pistol",
+        manufacturers: &["GD_Manufacturers.Manufacturers.Jakobs"],
+        parts: &[
+            (PartSlot::Body, &["GD_Weap_Pistol.Body.Pistol_Body_Jakobs"]),
+            (
+                PartSlot::Grip,
+                &[
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Jakobs",
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Vladof",
+                    "GD_Weap_Pistol.Grip.Pistol_Grip_Maliwan",
+                ],
+            ),
+            (
+                PartSlot::Barrel,
+                &["GD_Weap_Pistol.Barrel.Pistol_Barrel_Jakobs_Maggie"],
+            ),
+            (
+                PartSlot::Sight,
+                &[
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_Jakobs",
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_Vladof",
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_Dahl",
+                    "GD_Weap_Pistol.Sight.Pistol_Sight_None",
+                ],
+            ),
+            (
+                PartSlot::Elemental,
+                &["GD_Weap_Pistol.elemental.Pistol_Elemental_None"],
+            ),
+            (
+                PartSlot::Accessory1,
+                &[
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_Bayonet",
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_Body1_Accurate",
+                    "GD_Weap_Pistol.Accessory.Pistol_Accessory_Body2_Damage",
+                ],
+            ),
+            (
+                PartSlot::Material,
+                &["GD_Weap_Pistol.ManufacturerMaterials.Material_Jakobs_5_Maggie"],
+            ),
+            (PartSlot::Prefix, &["GD_Weap_Pistol.Prefix.Prefix_Maggie"]),
+            (PartSlot::Title, &["GD_Weap_Pistol.Title.Title_Maggie"]),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_Weap_AssaultRifle.A_Weapons.AR_Vladof_3_Rare",
+        is_weapon: true,
+        item_type: "GD_Weap_AssaultRifle.A_Weapons.WT_Vladof_AssaultRifle",
+        manufacturers: &["GD_Manufacturers.Manufacturers.Vladof"],
+        parts: &[
+            (
+                PartSlot::Body,
+                &["GD_Weap_AssaultRifle.Body.AR_Body_Vladof"],
+            ),
+            (
+                PartSlot::Grip,
+                &[
+                    "GD_Weap_AssaultRifle.Grip.AR_Grip_Vladof",
+                    "GD_Weap_AssaultRifle.Grip.AR_Grip_Dahl",
+                    "GD_Weap_AssaultRifle.Grip.AR_Grip_Torgue",
+                ],
+            ),
+            (
+                PartSlot::Barrel,
+                &[
+                    "GD_Weap_AssaultRifle.Barrel.AR_Barrel_Vladof",
+                    "GD_Weap_AssaultRifle.Barrel.AR_Barrel_Dahl",
+                    "GD_Weap_AssaultRifle.Barrel.AR_Barrel_Jakobs",
+                ],
+            ),
+            (
+                PartSlot::Sight,
+                &[
+                    "GD_Weap_AssaultRifle.Sight.AR_Sight_Vladof",
+                    "GD_Weap_AssaultRifle.Sight.AR_Sight_Dahl",
+                    "GD_Weap_AssaultRifle.Sight.AR_Sight_None",
+                ],
+            ),
+            (
+                PartSlot::Stock,
+                &[
+                    "GD_Weap_AssaultRifle.Stock.AR_Stock_Vladof",
+                    "GD_Weap_AssaultRifle.Stock.AR_Stock_Dahl",
+                    "GD_Weap_AssaultRifle.Stock.AR_Stock_Jakobs",
+                ],
+            ),
+            (
+                PartSlot::Elemental,
+                &[
+                    "GD_Weap_AssaultRifle.elemental.AR_Elemental_None",
+                    "GD_Weap_AssaultRifle.elemental.AR_Elemental_Fire",
+                    "GD_Weap_AssaultRifle.elemental.AR_Elemental_Shock",
+                    "GD_Weap_AssaultRifle.elemental.AR_Elemental_Corrosive",
+                    "GD_Weap_AssaultRifle.elemental.AR_Elemental_Slag",
+                ],
+            ),
+            (
+                PartSlot::Accessory1,
+                &[
+                    "GD_Weap_AssaultRifle.Accessory.AR_Accessory_None",
+                    "GD_Weap_AssaultRifle.Accessory.AR_Accessory_Bayonet",
+                    "GD_Weap_AssaultRifle.Accessory.AR_Accessory_Body1_Accurate",
+                ],
+            ),
+            (
+                PartSlot::Material,
+                &["GD_Weap_AssaultRifle.ManufacturerMaterials.Material_Vladof_3"],
+            ),
+            (
+                PartSlot::Prefix,
+                &["GD_Weap_AssaultRifle.Prefix.Prefix_Vladof_Rare"],
+            ),
+            (
+                PartSlot::Title,
+                &["GD_Weap_AssaultRifle.Title.Title_Vladof_Rare"],
+            ),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_Weap_SniperRifles.A_Weapons.Sniper_Hyperion_3_Rare",
+        is_weapon: true,
+        item_type: "GD_Weap_SniperRifles.A_Weapons.WT_Hyperion_Sniper",
+        manufacturers: &["GD_Manufacturers.Manufacturers.Hyperion"],
+        parts: &[
+            (
+                PartSlot::Body,
+                &["GD_Weap_SniperRifles.Body.SR_Body_Hyperion"],
+            ),
+            (
+                PartSlot::Grip,
+                &[
+                    "GD_Weap_SniperRifles.Grip.SR_Grip_Hyperion",
+                    "GD_Weap_SniperRifles.Grip.SR_Grip_Jakobs",
+                    "GD_Weap_SniperRifles.Grip.SR_Grip_Maliwan",
+                ],
+            ),
+            (
+                PartSlot::Barrel,
+                &[
+                    "GD_Weap_SniperRifles.Barrel.SR_Barrel_Hyperion",
+                    "GD_Weap_SniperRifles.Barrel.SR_Barrel_Jakobs",
+                    "GD_Weap_SniperRifles.Barrel.SR_Barrel_Vladof",
+                ],
+            ),
+            (
+                PartSlot::Sight,
+                &[
+                    "GD_Weap_SniperRifles.Sight.SR_Sight_Hyperion",
+                    "GD_Weap_SniperRifles.Sight.SR_Sight_Jakobs",
+                    "GD_Weap_SniperRifles.Sight.SR_Sight_Maliwan",
+                ],
+            ),
+            (
+                PartSlot::Stock,
+                &[
+                    "GD_Weap_SniperRifles.Stock.SR_Stock_Hyperion",
+                    "GD_Weap_SniperRifles.Stock.SR_Stock_Jakobs",
+                    "GD_Weap_SniperRifles.Stock.SR_Stock_Vladof",
+                ],
+            ),
+            (
+                PartSlot::Elemental,
+                &[
+                    "GD_Weap_SniperRifles.elemental.Sniper_Elemental_None",
+                    "GD_Weap_SniperRifles.elemental.Sniper_Elemental_Fire",
+                    "GD_Weap_SniperRifles.elemental.Sniper_Elemental_Shock",
+                    "GD_Weap_SniperRifles.elemental.Sniper_Elemental_Corrosive",
+                    "GD_Weap_SniperRifles.elemental.Sniper_Elemental_Slag",
+                ],
+            ),
+            (
+                PartSlot::Accessory1,
+                &[
+                    "GD_Weap_SniperRifles.Accessory.SR_Accessory_None",
+                    "GD_Weap_SniperRifles.Accessory.SR_Accessory_Body1_Accurate",
+                ],
+            ),
+            (
+                PartSlot::Material,
+                &["GD_Weap_SniperRifles.ManufacturerMaterials.Material_Hyperion_3"],
+            ),
+            (
+                PartSlot::Prefix,
+                &["GD_Weap_SniperRifles.Prefix.Prefix_Hyperion_Rare"],
+            ),
+            (
+                PartSlot::Title,
+                &["GD_Weap_SniperRifles.Title.Title_Hyperion_Rare"],
+            ),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_Weap_Shotgun.A_Weapons.SG_Torgue_3_Rare",
+        is_weapon: true,
+        item_type: "GD_Weap_Shotgun.A_Weapons.WT_Torgue_Shotgun",
+        manufacturers: &["GD_Manufacturers.Manufacturers.Torgue"],
+        parts: &[
+            (PartSlot::Body, &["GD_Weap_Shotgun.Body.SG_Body_Torgue"]),
+            (
+                PartSlot::Grip,
+                &[
+                    "GD_Weap_Shotgun.Grip.SG_Grip_Torgue",
+                    "GD_Weap_Shotgun.Grip.SG_Grip_Bandit",
+                    "GD_Weap_Shotgun.Grip.SG_Grip_Jakobs",
+                ],
+            ),
+            (
+                PartSlot::Barrel,
+                &[
+                    "GD_Weap_Shotgun.Barrel.SG_Barrel_Torgue",
+                    "GD_Weap_Shotgun.Barrel.SG_Barrel_Bandit",
+                    "GD_Weap_Shotgun.Barrel.SG_Barrel_Jakobs",
+                ],
+            ),
+            (
+                PartSlot::Sight,
+                &[
+                    "GD_Weap_Shotgun.Sight.SG_Sight_Torgue",
+                    "GD_Weap_Shotgun.Sight.SG_Sight_None",
+                ],
+            ),
+            (
+                PartSlot::Stock,
+                &[
+                    "GD_Weap_Shotgun.Stock.SG_Stock_Torgue",
+                    "GD_Weap_Shotgun.Stock.SG_Stock_Bandit",
+                ],
+            ),
+            (
+                PartSlot::Elemental,
+                &[
+                    "GD_Weap_Shotgun.elemental.SG_Elemental_None",
+                    "GD_Weap_Shotgun.elemental.SG_Elemental_Fire",
+                    "GD_Weap_Shotgun.elemental.SG_Elemental_Corrosive",
+                    "GD_Weap_Shotgun.elemental.SG_Elemental_Slag",
+                ],
+            ),
+            (
+                PartSlot::Accessory1,
+                &[
+                    "GD_Weap_Shotgun.Accessory.SG_Accessory_None",
+                    "GD_Weap_Shotgun.Accessory.SG_Accessory_Body1_Accurate",
+                ],
+            ),
+            (
+                PartSlot::Material,
+                &["GD_Weap_Shotgun.ManufacturerMaterials.Material_Torgue_3"],
+            ),
+            (
+                PartSlot::Prefix,
+                &["GD_Weap_Shotgun.Prefix.Prefix_Torgue_Rare"],
+            ),
+            (
+                PartSlot::Title,
+                &["GD_Weap_Shotgun.Title.Title_Torgue_Rare"],
+            ),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_ItemGrades.Shields.ItemGrade_Gear_Shield_Standard_03_Rare",
+        is_weapon: false,
+        item_type: "GD_Shields.A_Item.Shield_Standard",
+        manufacturers: &[
+            "GD_Manufacturers.Manufacturers.Anshin",
+            "GD_Manufacturers.Manufacturers.Pangolin",
+        ],
+        parts: &[
+            (
+                PartSlot::Alpha,
+                &[
+                    "GD_Shields.Body.Body_Anshin_Standard",
+                    "GD_Shields.Body.Body_Pangolin_Standard",
+                ],
+            ),
+            (
+                PartSlot::Beta,
+                &[
+                    "GD_Shields.Battery.Battery_Anshin",
+                    "GD_Shields.Battery.Battery_Pangolin",
+                ],
+            ),
+            (
+                PartSlot::Gamma,
+                &[
+                    "GD_Shields.Capacitor.Capacitor_Anshin",
+                    "GD_Shields.Capacitor.Capacitor_Pangolin",
+                ],
+            ),
+            (
+                PartSlot::Delta,
+                &[
+                    "GD_Shields.Accessory.Accessory_None",
+                    "GD_Shields.Accessory.Accessory_Capacity",
+                    "GD_Shields.Accessory.Accessory_Recharge",
+                ],
+            ),
+            (
+                PartSlot::Material,
+                &[
+                    "GD_Shields.ManufacturerMaterials.Material_Anshin_3",
+                    "GD_Shields.ManufacturerMaterials.Material_Pangolin_3",
+                ],
+            ),
+            (PartSlot::Title, &["GD_Shields.Title.Title_Standard_Rare"]),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_ItemGrades.GrenadeMods.ItemGrade_GrenadeMod_Standard_03_Rare",
+        is_weapon: false,
+        item_type: "GD_GrenadeMods.A_Item.GM_Standard",
+        manufacturers: &[
+            "GD_Manufacturers.Manufacturers.Bandit",
+            "GD_Manufacturers.Manufacturers.Dahl",
+        ],
+        parts: &[
+            (
+                PartSlot::Alpha,
+                &["GD_GrenadeMods.Payload.Payload_Standard"],
+            ),
+            (
+                PartSlot::Beta,
+                &[
+                    "GD_GrenadeMods.Delivery.Delivery_Standard",
+                    "GD_GrenadeMods.Delivery.Delivery_LongBow",
+                    "GD_GrenadeMods.Delivery.Delivery_Mirv",
+                ],
+            ),
+            (
+                PartSlot::Gamma,
+                &[
+                    "GD_GrenadeMods.Trigger.Trigger_Standard",
+                    "GD_GrenadeMods.Trigger.Trigger_Sticky",
+                ],
+            ),
+            (
+                PartSlot::Delta,
+                &[
+                    "GD_GrenadeMods.Damage.Damage_Grade1",
+                    "GD_GrenadeMods.Damage.Damage_Grade2",
+                    "GD_GrenadeMods.Damage.Damage_Grade3",
+                ],
+            ),
+            (
+                PartSlot::Epsilon,
+                &[
+                    "GD_GrenadeMods.Element.Element_None",
+                    "GD_GrenadeMods.Element.Element_Fire",
+                    "GD_GrenadeMods.Element.Element_Shock",
+                    "GD_GrenadeMods.Element.Element_Slag",
+                ],
+            ),
+            (
+                PartSlot::Material,
+                &[
+                    "GD_GrenadeMods.ManufacturerMaterials.Material_Bandit_3",
+                    "GD_GrenadeMods.ManufacturerMaterials.Material_Dahl_3",
+                ],
+            ),
+            (PartSlot::Title, &["GD_GrenadeMods.Title.Title_Standard"]),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_ItemGrades.ClassMods.BalDef_ClassMod_Assassin_03_Rare",
+        is_weapon: false,
+        item_type: "GD_ClassMods.A_Item_Assassin.ClassMod_Assassin_Sniper",
+        manufacturers: &[
+            "GD_Manufacturers.Manufacturers.Dahl",
+            "GD_Manufacturers.Manufacturers.Hyperion",
+            "GD_Manufacturers.Manufacturers.Jakobs",
+        ],
+        parts: &[
+            (
+                PartSlot::Alpha,
+                &[
+                    "GD_ClassMods.Specialization.Spec_Sniper",
+                    "GD_ClassMods.Specialization.Spec_Killer",
+                ],
+            ),
+            (
+                PartSlot::Beta,
+                &[
+                    "GD_ClassMods.StatPrimary.Stat_CritDamage",
+                    "GD_ClassMods.StatPrimary.Stat_GunDamage",
+                ],
+            ),
+            (
+                PartSlot::Gamma,
+                &[
+                    "GD_ClassMods.StatSecondary.Stat_SniperDamage",
+                    "GD_ClassMods.StatSecondary.Stat_ReloadSpeed",
+                ],
+            ),
+            (
+                PartSlot::Title,
+                &[
+                    "GD_ClassMods.Title.Title_Sniper",
+                    "GD_ClassMods.Title.Title_Killer",
+                ],
+            ),
+        ],
+    },
+    BalanceDefinition {
+        name: "GD_Artifacts.A_Item.A_Elemental_Status_Rare",
+        is_weapon: false,
+        item_type: "GD_Artifacts.A_Item.A_Elemental",
+        manufacturers: &["GD_Manufacturers.Manufacturers.Eridian"],
+        parts: &[
+            (
+                PartSlot::Alpha,
+                &[
+                    "GD_Artifacts.Body.Body_Fire",
+                    "GD_Artifacts.Body.Body_Shock",
+                    "GD_Artifacts.Body.Body_Corrosive",
+                ],
+            ),
+            (
+                PartSlot::Beta,
+                &[
+                    "GD_Artifacts.Upgrade.Upgrade_Chance",
+                    "GD_Artifacts.Upgrade.Upgrade_Damage",
+                ],
+            ),
+            (PartSlot::Title, &["GD_Artifacts.Title.Title_Elemental"]),
+        ],
+    },
+];
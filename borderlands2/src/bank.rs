@@ -0,0 +1,351 @@
+use crate::convert::{self, ConvertError};
+use crate::equipment::GearIndex;
+use crate::inventory;
+use crate::serial::decode_serial;
+use crate::BankSlot::BankSlot;
+use crate::PackedItemData::PackedItemData;
+use crate::PackedWeaponData::PackedWeaponData;
+use crate::PlayerMark::PlayerMark;
+use crate::QuickWeaponSlot::QuickWeaponSlot;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+use protobuf::Enum;
+
+/// Bank slots without any SDU.
+pub const BANK_BASE_SLOTS: i32 = 6;
+/// Bank slots added by every SDU level.
+pub const BANK_SLOTS_PER_LEVEL: i32 = 2;
+/// Highest bank SDU level sold by the black market.
+pub const MAX_BANK_LEVEL: i32 = 9;
+/// Position of the bank SDU in `BlackMarketUpgrades`.
+pub const BANK_UPGRADE_INDEX: usize = 8;
+
+#[derive(Debug)]
+pub enum BankError {
+    BankFull { msg: String },
+    BackpackFull { msg: String },
+    NoSuchGear { msg: String },
+    InvalidLevel { msg: String },
+    ConvertError { msg: String },
+}
+
+impl std::fmt::Display for BankError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BankError::BankFull { msg } => write!(f, "BankFull: {}", msg),
+            BankError::BackpackFull { msg } => write!(f, "BackpackFull: {}", msg),
+            BankError::NoSuchGear { msg } => write!(f, "NoSuchGear: {}", msg),
+            BankError::InvalidLevel { msg } => write!(f, "InvalidLevel: {}", msg),
+            BankError::ConvertError { msg } => write!(f, "ConvertError: {}", msg),
+        }
+    }
+}
+
+impl From<ConvertError> for BankError {
+    fn from(err: ConvertError) -> Self {
+        BankError::ConvertError {
+            msg: err.to_string(),
+        }
+    }
+}
+
+///
+/// Returns the number of bank slots, `MaxBankSlots`.
+///
+pub fn bank_capacity(save: &WillowTwoPlayerSaveGame) -> usize {
+    save.MaxBankSlots().max(0) as usize
+}
+
+///
+/// Returns the number of free bank slots.
+///
+pub fn bank_free(save: &WillowTwoPlayerSaveGame) -> usize {
+    bank_capacity(save).saturating_sub(save.BankSlots.len())
+}
+
+///
+/// Returns the bank SDU level stored in `BlackMarketUpgrades`.
+///
+pub fn bank_level(save: &WillowTwoPlayerSaveGame) -> i32 {
+    save.BlackMarketUpgrades
+        .get(BANK_UPGRADE_INDEX)
+        .copied()
+        .unwrap_or(0)
+}
+
+///
+/// Sets the bank SDU level and the matching `MaxBankSlots`.
+///
+/// Lowering the level below the number of stored serials is refused.
+///
+pub fn set_bank_level(save: &mut WillowTwoPlayerSaveGame, level: i32) -> Result<(), BankError> {
+    if !(0..=MAX_BANK_LEVEL).contains(&level) {
+        return Err(BankError::InvalidLevel {
+            msg: format!(
+                "Bank level {} is not between 0 and {}!",
+                level, MAX_BANK_LEVEL
+            ),
+        });
+    }
+    let capacity = BANK_BASE_SLOTS + level * BANK_SLOTS_PER_LEVEL;
+    if save.BankSlots.len() > capacity as usize {
+        return Err(BankError::InvalidLevel {
+            msg: format!(
+                "Bank level {} has {} slots but {} are in use!",
+                level,
+                capacity,
+                save.BankSlots.len()
+            ),
+        });
+    }
+
+    if save.BlackMarketUpgrades.len() <= BANK_UPGRADE_INDEX {
+        save.BlackMarketUpgrades.resize(BANK_UPGRADE_INDEX + 1, 0);
+    }
+    save.BlackMarketUpgrades[BANK_UPGRADE_INDEX] = level;
+    save.set_MaxBankSlots(capacity);
+    Ok(())
+}
+
+///
+/// Stores a serial in new bank slots and returns the index of the first.
+///
+/// A bank slot holds a single item, so a stack of `quantity` items takes `quantity` slots.
+///
+pub fn deposit_serial(
+    save: &mut WillowTwoPlayerSaveGame,
+    serial: Vec<u8>,
+    quantity: usize,
+) -> Result<usize, BankError> {
+    if bank_free(save) < quantity.max(1) {
+        return Err(BankError::BankFull {
+            msg: format!(
+                "{} bank slots are needed but only {} of {} are free!",
+                quantity.max(1),
+                bank_free(save),
+                save.MaxBankSlots()
+            ),
+        });
+    }
+    let first = save.BankSlots.len();
+    for _ in 0..quantity.max(1) {
+        let mut slot = BankSlot::new();
+        slot.set_InventorySerialNumber(serial.clone());
+        save.BankSlots.push(slot);
+    }
+    Ok(first)
+}
+
+///
+/// Moves gear from the backpack into the bank and returns the bank index.
+///
+/// Bank slots only store the serial, so the quick slot, mark and equipped state are dropped.
+/// A stack of items takes a slot for every item, see `deposit_serial`. Unpacked gear is
+/// packed with `seed`. The gear is removed from its list, which shifts the index of all gear
+/// after it.
+///
+pub fn deposit(
+    save: &mut WillowTwoPlayerSaveGame,
+    gear: GearIndex,
+    seed: i32,
+) -> Result<usize, BankError> {
+    let serial = match gear {
+        GearIndex::Weapon(index) => save
+            .WeaponData
+            .get(index)
+            .map(|weapon| convert::weapon_serial(weapon, seed).map(|serial| (serial, 1)))
+            .transpose()?,
+        GearIndex::PackedWeapon(index) => save
+            .PackedWeaponData
+            .get(index)
+            .map(|weapon| (weapon.InventorySerialNumber().to_vec(), 1)),
+        GearIndex::Item(index) => save
+            .ItemData
+            .get(index)
+            .map(|item| convert::item_serial(item, seed).map(|serial| (serial, item.Quantity())))
+            .transpose()?,
+        GearIndex::PackedItem(index) => save
+            .PackedItemData
+            .get(index)
+            .map(|item| (item.InventorySerialNumber().to_vec(), item.Quantity())),
+    };
+    let (serial, quantity) = match serial {
+        Some(serial) => serial,
+        None => {
+            return Err(BankError::NoSuchGear {
+                msg: format!("{:?} does not exist!", gear),
+            })
+        }
+    };
+
+    let bank_index = deposit_serial(save, serial, quantity.max(1) as usize)?;
+    match gear {
+        GearIndex::Weapon(index) => {
+            save.WeaponData.remove(index);
+        }
+        GearIndex::PackedWeapon(index) => {
+            save.PackedWeaponData.remove(index);
+        }
+        GearIndex::Item(index) => {
+            save.ItemData.remove(index);
+        }
+        GearIndex::PackedItem(index) => {
+            save.PackedItemData.remove(index);
+        }
+    }
+    Ok(bank_index)
+}
+
+///
+/// Converts a bank serial to a packed weapon in the backpack with the given mark.
+///
+pub fn bank_weapon(serial: &[u8], mark: PlayerMark) -> PackedWeaponData {
+    let mut weapon = PackedWeaponData::new();
+    weapon.set_InventorySerialNumber(serial.to_vec());
+    weapon.set_QuickSlot(QuickWeaponSlot::None);
+    weapon.set_Mark(mark);
+    weapon
+}
+
+///
+/// Converts bank serials to a packed item in the backpack with the given mark. `quantity` is
+/// the number of bank slots holding the serial.
+///
+pub fn bank_item(serial: &[u8], quantity: i32, mark: PlayerMark) -> PackedItemData {
+    let mut item = PackedItemData::new();
+    item.set_InventorySerialNumber(serial.to_vec());
+    item.set_Quantity(quantity);
+    item.set_Equipped(false);
+    item.set_Mark(mark.value());
+    item
+}
+
+///
+/// Moves gear from the bank into the backpack and returns its new index.
+///
+/// Bank slots do not store a mark, the gear gets `mark`, which is usually the mark it had
+/// when it was deposited. The gear is unequipped. An item is withdrawn together with all
+/// other bank slots holding the same serial, as one stack. Withdrawing into a full backpack
+/// is refused and leaves the bank untouched.
+///
+pub fn withdraw(
+    save: &mut WillowTwoPlayerSaveGame,
+    index: usize,
+    mark: PlayerMark,
+) -> Result<GearIndex, BankError> {
+    let serial = match save.BankSlots.get(index) {
+        Some(slot) => slot.InventorySerialNumber().to_vec(),
+        None => {
+            return Err(BankError::NoSuchGear {
+                msg: format!("Bank slot {} does not exist!", index),
+            })
+        }
+    };
+    let is_weapon = decode_serial(&serial)
+        .map_err(|err| BankError::ConvertError {
+            msg: err.to_string(),
+        })?
+        .is_weapon;
+    if inventory::backpack_free(save) == 0 {
+        return Err(BankError::BackpackFull {
+            msg: format!(
+                "All {} backpack slots are in use!",
+                inventory::backpack_capacity(save)
+            ),
+        });
+    }
+
+    if is_weapon {
+        save.BankSlots.remove(index);
+        save.PackedWeaponData.push(bank_weapon(&serial, mark));
+        Ok(GearIndex::PackedWeapon(save.PackedWeaponData.len() - 1))
+    } else {
+        let before = save.BankSlots.len();
+        save.BankSlots
+            .retain(|slot| slot.InventorySerialNumber() != serial.as_slice());
+        let quantity = (before - save.BankSlots.len()) as i32;
+        save.PackedItemData.push(bank_item(&serial, quantity, mark));
+        Ok(GearIndex::PackedItem(save.PackedItemData.len() - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bank_free, bank_level, deposit, set_bank_level, withdraw, BankError};
+    use crate::equipment::GearIndex;
+    use crate::load_test_save;
+    use crate::PlayerMark::PlayerMark;
+
+    #[test]
+    fn bank_level_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(bank_level(&save_game), 9);
+        assert_eq!(bank_free(&save_game), 14);
+
+        set_bank_level(&mut save_game, 2).unwrap();
+        assert_eq!(save_game.MaxBankSlots(), 10);
+        assert_eq!(save_game.BlackMarketUpgrades[8], 2);
+        assert!(set_bank_level(&mut save_game, 1).is_err());
+    }
+
+    #[test]
+    fn deposit_withdraw_test() {
+        let mut save_game = load_test_save();
+        let serial = save_game.PackedWeaponData[5]
+            .InventorySerialNumber()
+            .to_vec();
+        let weapons = save_game.PackedWeaponData.len();
+
+        let bank_index = deposit(&mut save_game, GearIndex::PackedWeapon(5), 0).unwrap();
+        assert_eq!(save_game.PackedWeaponData.len(), weapons - 1);
+        assert_eq!(
+            save_game.BankSlots[bank_index].InventorySerialNumber(),
+            serial
+        );
+
+        let gear = withdraw(&mut save_game, bank_index, PlayerMark::Favorite).unwrap();
+        assert_eq!(gear, GearIndex::PackedWeapon(weapons - 1));
+        assert_eq!(
+            save_game.PackedWeaponData[weapons - 1].Mark(),
+            PlayerMark::Favorite
+        );
+        assert_eq!(
+            save_game.PackedWeaponData[weapons - 1].InventorySerialNumber(),
+            serial
+        );
+        assert_eq!(save_game.BankSlots.len(), 10);
+
+        set_bank_level(&mut save_game, 2).unwrap();
+        assert!(matches!(
+            deposit(&mut save_game, GearIndex::PackedWeapon(0), 0),
+            Err(BankError::BankFull { .. })
+        ));
+    }
+
+    #[test]
+    fn deposit_stack_test() {
+        let mut save_game = load_test_save();
+        save_game.PackedItemData[0].set_Quantity(3);
+        let serial = save_game.PackedItemData[0].InventorySerialNumber().to_vec();
+        let items = save_game.PackedItemData.len();
+
+        let bank_index = deposit(&mut save_game, GearIndex::PackedItem(0), 0).unwrap();
+        assert_eq!(bank_index, 10);
+        assert_eq!(save_game.BankSlots.len(), 13);
+        assert!(save_game.BankSlots[10..]
+            .iter()
+            .all(|slot| slot.InventorySerialNumber() == serial));
+
+        let gear = withdraw(&mut save_game, 11, PlayerMark::Standard).unwrap();
+        assert_eq!(gear, GearIndex::PackedItem(items - 1));
+        assert_eq!(save_game.PackedItemData[items - 1].Quantity(), 3);
+        assert_eq!(save_game.BankSlots.len(), 10);
+
+        set_bank_level(&mut save_game, 3).unwrap();
+        save_game.PackedItemData[0].set_Quantity(3);
+        assert!(matches!(
+            deposit(&mut save_game, GearIndex::PackedItem(0), 0),
+            Err(BankError::BankFull { .. })
+        ));
+    }
+}
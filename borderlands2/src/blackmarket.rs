@@ -0,0 +1,216 @@
+use crate::ammo::{self, AmmoError, AmmoType, MAX_AMMO_LEVEL};
+use crate::bank::{self, BankError, BANK_UPGRADE_INDEX, MAX_BANK_LEVEL};
+use crate::inventory::{self, InventoryError, BACKPACK_UPGRADE_INDEX, MAX_BACKPACK_LEVEL};
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+#[derive(Debug)]
+pub enum BlackMarketError {
+    InvalidLevel { msg: String },
+}
+
+impl std::fmt::Display for BlackMarketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BlackMarketError::InvalidLevel { msg } => write!(f, "InvalidLevel: {}", msg),
+        }
+    }
+}
+
+impl From<AmmoError> for BlackMarketError {
+    fn from(err: AmmoError) -> Self {
+        match err {
+            AmmoError::InvalidLevel { msg } => BlackMarketError::InvalidLevel { msg },
+        }
+    }
+}
+
+impl From<InventoryError> for BlackMarketError {
+    fn from(err: InventoryError) -> Self {
+        match err {
+            InventoryError::InvalidLevel { msg } => BlackMarketError::InvalidLevel { msg },
+            err => BlackMarketError::InvalidLevel {
+                msg: err.to_string(),
+            },
+        }
+    }
+}
+
+impl From<BankError> for BlackMarketError {
+    fn from(err: BankError) -> Self {
+        match err {
+            BankError::InvalidLevel { msg } => BlackMarketError::InvalidLevel { msg },
+            err => BlackMarketError::InvalidLevel {
+                msg: err.to_string(),
+            },
+        }
+    }
+}
+
+///
+/// Upgrades sold by the black market, each stored as a level in `BlackMarketUpgrades`.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BlackMarketUpgrade {
+    Ammo(AmmoType),
+    Backpack,
+    Bank,
+}
+
+impl BlackMarketUpgrade {
+    pub const ALL: [BlackMarketUpgrade; 9] = [
+        BlackMarketUpgrade::Ammo(AmmoType::AssaultRifle),
+        BlackMarketUpgrade::Ammo(AmmoType::Pistol),
+        BlackMarketUpgrade::Ammo(AmmoType::Launcher),
+        BlackMarketUpgrade::Ammo(AmmoType::Shotgun),
+        BlackMarketUpgrade::Ammo(AmmoType::SMG),
+        BlackMarketUpgrade::Ammo(AmmoType::Sniper),
+        BlackMarketUpgrade::Ammo(AmmoType::Grenade),
+        BlackMarketUpgrade::Backpack,
+        BlackMarketUpgrade::Bank,
+    ];
+
+    ///
+    /// Returns the position of the upgrade in `BlackMarketUpgrades`.
+    ///
+    pub fn index(self) -> usize {
+        match self {
+            BlackMarketUpgrade::Ammo(ammo) => ammo.upgrade_index(),
+            BlackMarketUpgrade::Backpack => BACKPACK_UPGRADE_INDEX,
+            BlackMarketUpgrade::Bank => BANK_UPGRADE_INDEX,
+        }
+    }
+
+    ///
+    /// Returns the highest level the black market sells.
+    ///
+    pub fn max_level(self) -> i32 {
+        match self {
+            BlackMarketUpgrade::Ammo(_) => MAX_AMMO_LEVEL,
+            BlackMarketUpgrade::Backpack => MAX_BACKPACK_LEVEL,
+            BlackMarketUpgrade::Bank => MAX_BANK_LEVEL,
+        }
+    }
+}
+
+///
+/// Returns the purchased level of an upgrade, missing entries count as 0.
+///
+pub fn upgrade_level(save: &WillowTwoPlayerSaveGame, upgrade: BlackMarketUpgrade) -> i32 {
+    save.BlackMarketUpgrades
+        .get(upgrade.index())
+        .copied()
+        .unwrap_or(0)
+}
+
+///
+/// Returns every upgrade with its purchased level.
+///
+pub fn upgrades(save: &WillowTwoPlayerSaveGame) -> Vec<(BlackMarketUpgrade, i32)> {
+    BlackMarketUpgrade::ALL
+        .iter()
+        .map(|upgrade| (*upgrade, upgrade_level(save, *upgrade)))
+        .collect()
+}
+
+///
+/// Sets the purchased level of an upgrade and the state depending on it.
+///
+/// Ammo upgrades set `ResourceData.UpgradeLevel`, the backpack `InventorySlotMax` and the
+/// bank `MaxBankSlots`. Lowering the backpack or bank below their content is refused.
+///
+pub fn set_upgrade_level(
+    save: &mut WillowTwoPlayerSaveGame,
+    upgrade: BlackMarketUpgrade,
+    level: i32,
+) -> Result<(), BlackMarketError> {
+    match upgrade {
+        BlackMarketUpgrade::Ammo(ammo) => {
+            if !(0..=upgrade.max_level()).contains(&level) {
+                return Err(BlackMarketError::InvalidLevel {
+                    msg: format!(
+                        "The ammo upgrade level {} is outside of 0 to {}!",
+                        level,
+                        upgrade.max_level()
+                    ),
+                });
+            }
+            ammo::set_ammo_level(save, ammo, level)?;
+        }
+        BlackMarketUpgrade::Backpack => inventory::set_backpack_level(save, level)?,
+        BlackMarketUpgrade::Bank => bank::set_bank_level(save, level)?,
+    }
+    Ok(())
+}
+
+///
+/// Applies the levels in `BlackMarketUpgrades` to the state depending on them, e.g. after
+/// editing the list directly.
+///
+/// The level of an ammo SDU is the number of SDUs bought, see `ammo::set_ammo_level`.
+/// Applying the upgrades of an unedited save changes nothing.
+///
+pub fn apply_upgrades(save: &mut WillowTwoPlayerSaveGame) -> Result<(), BlackMarketError> {
+    let mut working = save.clone();
+    for (upgrade, level) in upgrades(save) {
+        set_upgrade_level(&mut working, upgrade, level)?;
+    }
+    *save = working;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_upgrades, set_upgrade_level, upgrade_level, upgrades, BlackMarketUpgrade};
+    use crate::ammo::{self, AmmoType};
+    use crate::load_test_save;
+
+    #[test]
+    fn upgrade_level_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(upgrades(&save_game).len(), 9);
+        assert_eq!(upgrade_level(&save_game, BlackMarketUpgrade::Bank), 9);
+
+        let sniper = BlackMarketUpgrade::Ammo(AmmoType::Sniper);
+        set_upgrade_level(&mut save_game, sniper, 3).unwrap();
+        assert_eq!(save_game.BlackMarketUpgrades[5], 3);
+        assert_eq!(ammo::ammo_level(&save_game, AmmoType::Sniper), 3);
+        assert_eq!(ammo::ammo_capacity(&save_game, AmmoType::Sniper), 84);
+        assert!(set_upgrade_level(&mut save_game, sniper, 7).is_ok());
+        assert!(set_upgrade_level(&mut save_game, sniper, 8).is_err());
+
+        set_upgrade_level(&mut save_game, sniper, 1).unwrap();
+        let one = ammo::ammo_capacity(&save_game, AmmoType::Sniper);
+        set_upgrade_level(&mut save_game, sniper, 0).unwrap();
+        let none = ammo::ammo_capacity(&save_game, AmmoType::Sniper);
+        assert_eq!((none, one), (48, 60));
+        assert_eq!(save_game.BlackMarketUpgrades[5], 0);
+
+        set_upgrade_level(&mut save_game, BlackMarketUpgrade::Backpack, 9).unwrap();
+        assert_eq!(save_game.InventorySlotData.InventorySlotMax(), 39);
+        assert!(set_upgrade_level(&mut save_game, BlackMarketUpgrade::Bank, 1).is_err());
+        assert_eq!(save_game.MaxBankSlots(), 24);
+    }
+
+    #[test]
+    fn apply_unchanged_upgrades_test() {
+        let mut save_game = load_test_save();
+        let original = save_game.clone();
+        apply_upgrades(&mut save_game).unwrap();
+        assert_eq!(save_game, original);
+    }
+
+    #[test]
+    fn apply_upgrades_test() {
+        let mut save_game = load_test_save();
+        save_game.BlackMarketUpgrades = vec![7, 7, 7, 7, 7, 7, 7, 9, 2];
+        apply_upgrades(&mut save_game).unwrap();
+        assert_eq!(save_game.MaxBankSlots(), 10);
+        for ammo in AmmoType::ALL {
+            assert_eq!(ammo::ammo_level(&save_game, ammo), 7);
+        }
+
+        save_game.BlackMarketUpgrades[8] = 0;
+        assert!(apply_upgrades(&mut save_game).is_err());
+        assert_eq!(save_game.MaxBankSlots(), 10);
+    }
+}
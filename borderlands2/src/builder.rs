@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+
+use crate::assets::{self, BalanceDefinition, PartSlot};
+use crate::convert;
+use crate::ItemData::ItemData;
+use crate::PlayerMark::PlayerMark;
+use crate::QuickWeaponSlot::QuickWeaponSlot;
+use crate::WeaponData::WeaponData;
+
+#[derive(Debug)]
+pub enum GearBuildError {
+    UnknownBalance { msg: String },
+    InvalidPart { msg: String },
+    InvalidManufacturer { msg: String },
+    WrongKind { msg: String },
+    UnknownAsset { msg: String },
+    InvalidLevel { msg: String },
+}
+
+impl std::fmt::Display for GearBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GearBuildError::UnknownBalance { msg } => write!(f, "UnknownBalance: {}", msg),
+            GearBuildError::InvalidPart { msg } => write!(f, "InvalidPart: {}", msg),
+            GearBuildError::InvalidManufacturer { msg } => {
+                write!(f, "InvalidManufacturer: {}", msg)
+            }
+            GearBuildError::WrongKind { msg } => write!(f, "WrongKind: {}", msg),
+            GearBuildError::UnknownAsset { msg } => write!(f, "UnknownAsset: {}", msg),
+            GearBuildError::InvalidLevel { msg } => write!(f, "InvalidLevel: {}", msg),
+        }
+    }
+}
+
+///
+/// Builds weapons and items from a balance definition.
+///
+/// A new builder starts with the first valid choice for every part slot of the balance.
+/// Every part can then be replaced by another choice offered for its slot.
+///
+#[derive(Clone, Debug)]
+pub struct GearBuilder {
+    balance: &'static BalanceDefinition,
+    manufacturer: &'static str,
+    parts: HashMap<PartSlot, &'static str>,
+    level: i32,
+    seed: i32,
+}
+
+impl GearBuilder {
+    ///
+    /// Creates a builder for the balance with the given full path.
+    ///
+    pub fn new(balance: &str) -> Result<GearBuilder, GearBuildError> {
+        let balance = match assets::balance(balance) {
+            Some(balance) => balance,
+            None => {
+                return Err(GearBuildError::UnknownBalance {
+                    msg: format!("No balance definition named {}!", balance),
+                })
+            }
+        };
+        let parts = balance
+            .parts
+            .iter()
+            .filter_map(|(slot, choices)| choices.first().map(|part| (*slot, *part)))
+            .collect();
+        Ok(GearBuilder {
+            balance,
+            manufacturer: balance.manufacturers.first().copied().unwrap_or(""),
+            parts,
+            level: 1,
+            seed: 0,
+        })
+    }
+
+    pub fn balance(&self) -> &'static BalanceDefinition {
+        self.balance
+    }
+
+    ///
+    /// Returns the part slots used by the balance in serial order.
+    ///
+    pub fn slots(&self) -> Vec<PartSlot> {
+        self.serial_slots()
+            .iter()
+            .filter(|slot| !self.balance.choices(**slot).is_empty())
+            .copied()
+            .collect()
+    }
+
+    ///
+    /// Returns the valid parts for a slot.
+    ///
+    pub fn choices(&self, slot: PartSlot) -> &'static [&'static str] {
+        self.balance.choices(slot)
+    }
+
+    ///
+    /// Returns the currently selected part of a slot.
+    ///
+    pub fn selected(&self, slot: PartSlot) -> Option<&'static str> {
+        self.parts.get(&slot).copied()
+    }
+
+    ///
+    /// Selects a part for a slot. The part has to be one of the choices of the slot.
+    ///
+    pub fn part(&mut self, slot: PartSlot, part: &str) -> Result<&mut Self, GearBuildError> {
+        match self.choices(slot).iter().find(|choice| **choice == part) {
+            Some(choice) => {
+                self.parts.insert(slot, choice);
+                Ok(self)
+            }
+            None => Err(GearBuildError::InvalidPart {
+                msg: format!(
+                    "{} is not a valid {:?} part for {}!",
+                    part, slot, self.balance.name
+                ),
+            }),
+        }
+    }
+
+    ///
+    /// Removes the part of a slot.
+    ///
+    pub fn clear_part(&mut self, slot: PartSlot) -> &mut Self {
+        self.parts.remove(&slot);
+        self
+    }
+
+    ///
+    /// Selects the manufacturer. It has to be one of the manufacturers of the balance.
+    ///
+    pub fn manufacturer(&mut self, manufacturer: &str) -> Result<&mut Self, GearBuildError> {
+        match self
+            .balance
+            .manufacturers
+            .iter()
+            .find(|choice| **choice == manufacturer)
+        {
+            Some(choice) => {
+                self.manufacturer = choice;
+                Ok(self)
+            }
+            None => Err(GearBuildError::InvalidManufacturer {
+                msg: format!(
+                    "{} is not a valid manufacturer for {}!",
+                    manufacturer, self.balance.name
+                ),
+            }),
+        }
+    }
+
+    ///
+    /// Sets the level of the gear, which is both the game stage and the manufacturer grade.
+    ///
+    /// The serial stores the level in 7 bits, so it has to be between 0 and 127.
+    ///
+    pub fn level(&mut self, level: i32) -> Result<&mut Self, GearBuildError> {
+        if !(0..=convert::MAX_SERIAL_LEVEL).contains(&level) {
+            return Err(GearBuildError::InvalidLevel {
+                msg: format!(
+                    "Level {} is outside of 0 to {}!",
+                    level,
+                    convert::MAX_SERIAL_LEVEL
+                ),
+            });
+        }
+        self.level = level;
+        Ok(self)
+    }
+
+    ///
+    /// Sets the seed used to encrypt the packed serial.
+    ///
+    pub fn seed(&mut self, seed: i32) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+
+    fn serial_slots(&self) -> &'static [PartSlot; 11] {
+        if self.balance.is_weapon {
+            &PartSlot::WEAPON
+        } else {
+            &PartSlot::ITEM
+        }
+    }
+
+    fn part_name(&self, slot: PartSlot) -> String {
+        self.selected(slot).unwrap_or("").to_string()
+    }
+
+    ///
+    /// Builds the weapon in its unpacked form.
+    ///
+    pub fn build_weapon(&self) -> Result<WeaponData, GearBuildError> {
+        if !self.balance.is_weapon {
+            return Err(GearBuildError::WrongKind {
+                msg: format!("{} is not a weapon balance!", self.balance.name),
+            });
+        }
+        let mut weapon = WeaponData::new();
+        weapon.set_Balance(self.balance.name.to_string());
+        weapon.set_Manufacturer(self.manufacturer.to_string());
+        weapon.set_Type(self.balance.item_type.to_string());
+        weapon.set_BodyPart(self.part_name(PartSlot::Body));
+        weapon.set_GripPart(self.part_name(PartSlot::Grip));
+        weapon.set_BarrelPart(self.part_name(PartSlot::Barrel));
+        weapon.set_SightPart(self.part_name(PartSlot::Sight));
+        weapon.set_StockPart(self.part_name(PartSlot::Stock));
+        weapon.set_Unknown9(String::new());
+        weapon.set_Unknown10(String::new());
+        weapon.set_Unknown11(String::new());
+        weapon.set_Unknown12(String::new());
+        weapon.set_MaterialPart(self.part_name(PartSlot::Material));
+        weapon.set_PrefixPart(self.part_name(PartSlot::Prefix));
+        weapon.set_TitlePart(self.part_name(PartSlot::Title));
+        weapon.set_Unknown16(self.level);
+        weapon.set_ManufacturerGradeIndex(self.level);
+        weapon.set_QuickSlot(QuickWeaponSlot::None);
+        weapon.set_Mark(PlayerMark::Standard);
+        weapon.set_ElementalPart(self.part_name(PartSlot::Elemental));
+        weapon.set_Accessory1Part(self.part_name(PartSlot::Accessory1));
+        weapon.set_Accessory2Part(self.part_name(PartSlot::Accessory2));
+        Ok(weapon)
+    }
+
+    ///
+    /// Builds the item in its unpacked form.
+    ///
+    pub fn build_item(&self) -> Result<ItemData, GearBuildError> {
+        if self.balance.is_weapon {
+            return Err(GearBuildError::WrongKind {
+                msg: format!("{} is not an item balance!", self.balance.name),
+            });
+        }
+        let mut item = ItemData::new();
+        item.set_Balance(self.balance.name.to_string());
+        item.set_Type(self.balance.item_type.to_string());
+        item.set_AlphaPart(self.part_name(PartSlot::Alpha));
+        item.set_BetaPart(self.part_name(PartSlot::Beta));
+        item.set_GammaPart(self.part_name(PartSlot::Gamma));
+        item.set_DeltaPart(self.part_name(PartSlot::Delta));
+        item.set_EpislonPart(self.part_name(PartSlot::Epsilon));
+        item.set_ZetaPart(self.part_name(PartSlot::Zeta));
+        item.set_EtaPart(self.part_name(PartSlot::Eta));
+        item.set_ThetaPart(self.part_name(PartSlot::Theta));
+        item.set_MaterialPart(self.part_name(PartSlot::Material));
+        item.set_Manufacturer(self.manufacturer.to_string());
+        item.set_PrefixPart(self.part_name(PartSlot::Prefix));
+        item.set_TitlePart(self.part_name(PartSlot::Title));
+        item.set_Quantity(1);
+        item.set_ManufacturerGradeIndex(self.level);
+        item.set_Equipped(false);
+        item.set_Mark(PlayerMark::Standard);
+        Ok(item)
+    }
+
+    ///
+    /// Builds the packed `InventorySerialNumber` of the weapon or item.
+    ///
+    pub fn build_serial(&self) -> Result<Vec<u8>, GearBuildError> {
+        let serial = if self.balance.is_weapon {
+            convert::weapon_serial(&self.build_weapon()?, self.seed)
+        } else {
+            convert::item_serial(&self.build_item()?, self.seed)
+        };
+        serial.map_err(|err| GearBuildError::UnknownAsset {
+            msg: err.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GearBuilder;
+    use crate::assets::{self, PartSlot};
+    use crate::serial::{asset_kinds, decode_serial};
+
+    #[test]
+    fn build_weapon_test() {
+        let mut builder =
+            GearBuilder::new("GD_Weap_Pistol.A_Weapons.Pistol_Vladof_3_Rare").unwrap();
+        builder
+            .part(
+                PartSlot::Elemental,
+                "GD_Weap_Pistol.elemental.Pistol_Elemental_Slag",
+            )
+            .unwrap()
+            .level(37)
+            .unwrap();
+        assert!(builder.level(128).is_err());
+        assert!(builder.level(-1).is_err());
+        assert!(builder
+            .part(
+                PartSlot::Barrel,
+                "GD_Weap_Pistol.Barrel.Pistol_Barrel_Jakobs_Maggie"
+            )
+            .is_err());
+
+        let weapon = builder.build_weapon().unwrap();
+        assert_eq!(
+            weapon.ElementalPart(),
+            "GD_Weap_Pistol.elemental.Pistol_Elemental_Slag"
+        );
+        assert_eq!(weapon.ManufacturerGradeIndex(), 37);
+        assert!(builder.build_item().is_err());
+    }
+
+    #[test]
+    fn build_serial_test() {
+        for balance in assets::balances() {
+            let mut builder = GearBuilder::new(balance.name).unwrap();
+            builder.level(12).unwrap().seed(0x1234_5678);
+            let serial = decode_serial(&builder.build_serial().unwrap()).unwrap();
+            let (_, balance_kind, _) = asset_kinds(balance.is_weapon);
+            assert_eq!(serial.is_weapon, balance.is_weapon);
+            assert_eq!(serial.seed, 0x1234_5678);
+            assert_eq!(serial.game_stage, 12);
+            assert_eq!(
+                assets::asset_name(serial.set_id, balance_kind, &serial.balance).unwrap(),
+                balance.name
+            );
+        }
+    }
+}
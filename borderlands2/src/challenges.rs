@@ -0,0 +1,529 @@
+use std::convert::TryInto;
+
+use crate::OneOffLevelChallengeData::OneOffLevelChallengeData;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+/// Version of the `StatsData` layout this module reads and writes.
+pub const STATS_VERSION: u32 = 4;
+/// Type tag of an int32 value in `StatsData`.
+const STAT_TYPE_INT: u8 = 1;
+/// Size of a stat entry in `StatsData`: id, two tagged int32 values.
+const STAT_ENTRY_SIZE: usize = 12;
+/// Bits of a `LevelChallengeUnlocks` entry holding the unlocked challenges of the level.
+const LEVEL_UNLOCK_BITS: i32 = 12;
+
+#[derive(Debug)]
+pub enum ChallengeError {
+    InvalidStatsData { msg: String },
+    NoSuchChallenge { msg: String },
+    NoSuchStat { msg: String },
+    InvalidGoal { msg: String },
+}
+
+impl std::fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChallengeError::InvalidStatsData { msg } => write!(f, "InvalidStatsData: {}", msg),
+            ChallengeError::NoSuchChallenge { msg } => write!(f, "NoSuchChallenge: {}", msg),
+            ChallengeError::NoSuchStat { msg } => write!(f, "NoSuchStat: {}", msg),
+            ChallengeError::InvalidGoal { msg } => write!(f, "InvalidGoal: {}", msg),
+        }
+    }
+}
+
+///
+/// Counter backing the tiers of a challenge, stored in `StatsData`.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StatEntry {
+    pub id: u16,
+    pub value: i32,
+    /// Value at the last challenge prestige, progress is counted from here.
+    pub baseline: i32,
+}
+
+impl StatEntry {
+    ///
+    /// Returns the progress made since the last challenge prestige.
+    ///
+    pub fn progress(&self) -> i32 {
+        self.value - self.baseline
+    }
+}
+
+///
+/// Definition of a challenge from the game data.
+///
+/// The save only lists challenges by path, the stat counting a challenge and its goals come
+/// from the game, so callers pass them in. No table is embedded, `StatsData` holds the stat
+/// ids but not the challenges they count.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChallengeDefinition {
+    /// Full path as stored in `ChallengeData.Challenge`.
+    pub name: String,
+    pub display_name: String,
+    /// Id of the stat in `StatsData` counting the progress.
+    pub stat_id: u16,
+    /// Progress needed for every tier, in ascending order.
+    pub goals: Vec<i32>,
+}
+
+///
+/// Challenge listed in `ChallengeList`.
+///
+#[derive(Debug, PartialEq, Eq)]
+pub struct Challenge<'a> {
+    /// Full path as stored in `ChallengeData.Challenge`.
+    pub name: &'a str,
+    /// Display name of the definition, or derived from the path, see `display_name`.
+    pub display_name: String,
+    pub dlc_package_id: i32,
+    /// Progress since the last prestige, `None` without a definition.
+    pub progress: Option<i32>,
+    /// Tiers reached, `None` without a definition.
+    pub tier: Option<usize>,
+}
+
+///
+/// Returns a readable name derived from a challenge path, e.g. `Duels Won Haters Gonna Hate`
+/// for `GD_Challenges.Dueling.DuelsWon_HatersGonnaHate`. Used for challenges without a
+/// definition.
+///
+pub fn display_name(challenge: &str) -> String {
+    let name = challenge.rsplit('.').next().unwrap_or(challenge);
+    let mut display_name = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c == '_' {
+            previous = None;
+            continue;
+        }
+        let boundary = match previous {
+            None => !display_name.is_empty(),
+            Some(previous) => {
+                (c.is_uppercase() && !previous.is_uppercase())
+                    || (c.is_ascii_digit() && !previous.is_ascii_digit())
+            }
+        };
+        if boundary {
+            display_name.push(' ');
+        }
+        display_name.push(c);
+        previous = Some(c);
+    }
+    display_name
+}
+
+///
+/// Returns the challenges listed in `ChallengeList` with the progress of those that have a
+/// definition.
+///
+pub fn challenges<'a>(
+    save: &'a WillowTwoPlayerSaveGame,
+    definitions: &[ChallengeDefinition],
+) -> Result<Vec<Challenge<'a>>, ChallengeError> {
+    let entries = stats(save)?;
+    Ok(save
+        .ChallengeList
+        .iter()
+        .map(|challenge| {
+            let definition = definitions
+                .iter()
+                .find(|definition| definition.name == challenge.Challenge());
+            let progress = definition.and_then(|definition| {
+                entries
+                    .iter()
+                    .find(|stat| stat.id == definition.stat_id)
+                    .map(StatEntry::progress)
+            });
+            Challenge {
+                name: challenge.Challenge(),
+                display_name: match definition {
+                    Some(definition) => definition.display_name.clone(),
+                    None => display_name(challenge.Challenge()),
+                },
+                dlc_package_id: challenge.DLCPackageId(),
+                progress,
+                tier: definition
+                    .zip(progress)
+                    .map(|(definition, progress)| tier(progress, &definition.goals)),
+            }
+        })
+        .collect())
+}
+
+fn definition<'a>(
+    definitions: &'a [ChallengeDefinition],
+    challenge: &str,
+) -> Result<&'a ChallengeDefinition, ChallengeError> {
+    definitions
+        .iter()
+        .find(|definition| definition.name == challenge)
+        .ok_or_else(|| ChallengeError::NoSuchChallenge {
+            msg: format!("{} has no definition!", challenge),
+        })
+}
+
+fn invalid_stats(msg: &str) -> ChallengeError {
+    ChallengeError::InvalidStatsData {
+        msg: msg.to_string(),
+    }
+}
+
+///
+/// Parses `StatsData`.
+///
+/// The blob starts with the version, the size of the rest and the number of entries. Every
+/// entry is a u16 id followed by the type tagged value and baseline.
+///
+pub fn stats(save: &WillowTwoPlayerSaveGame) -> Result<Vec<StatEntry>, ChallengeError> {
+    let data = save.StatsData();
+    if data.len() < 10 {
+        return Err(invalid_stats("StatsData is too short!"));
+    }
+    let version = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    if version != STATS_VERSION {
+        return Err(ChallengeError::InvalidStatsData {
+            msg: format!("StatsData version {} is not supported!", version),
+        });
+    }
+    let size = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+    let count = u16::from_le_bytes(data[8..10].try_into().unwrap()) as usize;
+    if size != data.len() - 8 || size != 2 + count * STAT_ENTRY_SIZE {
+        return Err(invalid_stats("StatsData size does not match its entries!"));
+    }
+
+    data[10..]
+        .chunks(STAT_ENTRY_SIZE)
+        .map(|entry| {
+            if entry[2] != STAT_TYPE_INT || entry[7] != STAT_TYPE_INT {
+                return Err(invalid_stats(
+                    "StatsData holds a stat that is not an int32!",
+                ));
+            }
+            Ok(StatEntry {
+                id: u16::from_le_bytes(entry[0..2].try_into().unwrap()),
+                value: i32::from_le_bytes(entry[3..7].try_into().unwrap()),
+                baseline: i32::from_le_bytes(entry[8..12].try_into().unwrap()),
+            })
+        })
+        .collect()
+}
+
+///
+/// Writes the stats back to `StatsData`.
+///
+pub fn set_stats(save: &mut WillowTwoPlayerSaveGame, stats: &[StatEntry]) {
+    let mut data = Vec::with_capacity(10 + stats.len() * STAT_ENTRY_SIZE);
+    data.extend_from_slice(&STATS_VERSION.to_le_bytes());
+    data.extend_from_slice(&((2 + stats.len() * STAT_ENTRY_SIZE) as u32).to_le_bytes());
+    data.extend_from_slice(&(stats.len() as u16).to_le_bytes());
+    for stat in stats {
+        data.extend_from_slice(&stat.id.to_le_bytes());
+        data.push(STAT_TYPE_INT);
+        data.extend_from_slice(&stat.value.to_le_bytes());
+        data.push(STAT_TYPE_INT);
+        data.extend_from_slice(&stat.baseline.to_le_bytes());
+    }
+    save.set_StatsData(data);
+}
+
+///
+/// Returns a stat by its id.
+///
+pub fn stat(save: &WillowTwoPlayerSaveGame, id: u16) -> Result<StatEntry, ChallengeError> {
+    stats(save)?
+        .into_iter()
+        .find(|stat| stat.id == id)
+        .ok_or_else(|| ChallengeError::NoSuchStat {
+            msg: format!("StatsData has no stat {}!", id),
+        })
+}
+
+fn set_progress(
+    save: &mut WillowTwoPlayerSaveGame,
+    id: u16,
+    progress: i32,
+) -> Result<(), ChallengeError> {
+    let mut entries = stats(save)?;
+    let entry = entries
+        .iter_mut()
+        .find(|stat| stat.id == id)
+        .ok_or_else(|| ChallengeError::NoSuchStat {
+            msg: format!("StatsData has no stat {}!", id),
+        })?;
+    entry.value = entry.baseline.saturating_add(progress);
+    set_stats(save, &entries);
+    Ok(())
+}
+
+///
+/// Returns the number of tiers reached with the given progress.
+///
+/// `goals` are the progress needed for every tier, in ascending order.
+///
+pub fn tier(progress: i32, goals: &[i32]) -> usize {
+    goals.iter().take_while(|goal| progress >= **goal).count()
+}
+
+///
+/// Returns the tiers reached by a challenge, see `tier`.
+///
+pub fn challenge_tier(
+    save: &WillowTwoPlayerSaveGame,
+    definitions: &[ChallengeDefinition],
+    challenge: &str,
+) -> Result<usize, ChallengeError> {
+    let definition = definition(definitions, challenge)?;
+    Ok(tier(
+        stat(save, definition.stat_id)?.progress(),
+        &definition.goals,
+    ))
+}
+
+///
+/// Completes a challenge up to a tier, progress beyond it is kept.
+///
+pub fn complete_challenge(
+    save: &mut WillowTwoPlayerSaveGame,
+    definitions: &[ChallengeDefinition],
+    challenge: &str,
+    tier: usize,
+) -> Result<(), ChallengeError> {
+    let definition = definition(definitions, challenge)?;
+    let goal = match tier
+        .checked_sub(1)
+        .and_then(|index| definition.goals.get(index))
+    {
+        Some(goal) => *goal,
+        None => {
+            return Err(ChallengeError::InvalidGoal {
+                msg: format!(
+                    "Tier {} of {} is outside of 1 to {}!",
+                    tier,
+                    challenge,
+                    definition.goals.len()
+                ),
+            })
+        }
+    };
+    let progress = stat(save, definition.stat_id)?.progress();
+    set_progress(save, definition.stat_id, progress.max(goal))
+}
+
+///
+/// Resets a challenge to no progress.
+///
+pub fn reset_challenge(
+    save: &mut WillowTwoPlayerSaveGame,
+    definitions: &[ChallengeDefinition],
+    challenge: &str,
+) -> Result<(), ChallengeError> {
+    let definition = definition(definitions, challenge)?;
+    set_progress(save, definition.stat_id, 0)
+}
+
+///
+/// Returns the level challenge unlocks as level id and bitset of unlocked challenges.
+///
+/// Every `LevelChallengeUnlocks` entry keeps the level in its upper bits and the unlocked
+/// challenges of the level in its lower `LEVEL_UNLOCK_BITS` bits.
+///
+pub fn level_challenge_unlocks(save: &WillowTwoPlayerSaveGame) -> Vec<(i32, u32)> {
+    save.LevelChallengeUnlocks
+        .iter()
+        .map(|entry| {
+            (
+                entry >> LEVEL_UNLOCK_BITS,
+                (entry & ((1 << LEVEL_UNLOCK_BITS) - 1)) as u32,
+            )
+        })
+        .collect()
+}
+
+///
+/// Unlocks or locks a level challenge, levels without an entry get one.
+///
+pub fn set_level_challenge_unlocked(
+    save: &mut WillowTwoPlayerSaveGame,
+    level: i32,
+    challenge: u32,
+    unlocked: bool,
+) -> Result<(), ChallengeError> {
+    if challenge >= LEVEL_UNLOCK_BITS as u32 {
+        return Err(ChallengeError::InvalidGoal {
+            msg: format!(
+                "Level challenge {} is outside of 0 to {}!",
+                challenge,
+                LEVEL_UNLOCK_BITS - 1
+            ),
+        });
+    }
+    let index = match save
+        .LevelChallengeUnlocks
+        .iter()
+        .position(|entry| entry >> LEVEL_UNLOCK_BITS == level)
+    {
+        Some(index) => index,
+        None => {
+            save.LevelChallengeUnlocks.push(level << LEVEL_UNLOCK_BITS);
+            save.LevelChallengeUnlocks.len() - 1
+        }
+    };
+    let bit = 1 << challenge;
+    if unlocked {
+        save.LevelChallengeUnlocks[index] |= bit;
+    } else {
+        save.LevelChallengeUnlocks[index] &= !bit;
+    }
+    Ok(())
+}
+
+///
+/// Returns whether a one-off challenge of a level is completed.
+///
+/// One-off challenges are bits of `OneOffLevelChallengeData.Completion`, 32 per entry.
+///
+pub fn is_one_off_completed(
+    save: &WillowTwoPlayerSaveGame,
+    package_id: i32,
+    content_id: i32,
+    challenge: usize,
+) -> bool {
+    save.OneOffLevelChallengeCompletion
+        .iter()
+        .find(|data| data.PackageId() == package_id && data.ContentId() == content_id)
+        .and_then(|data| data.Completion.get(challenge / 32))
+        .is_some_and(|bits| bits & (1 << (challenge % 32)) != 0)
+}
+
+///
+/// Completes or resets a one-off challenge of a level.
+///
+/// Resetting a challenge that is not completed leaves `OneOffLevelChallengeCompletion` as it
+/// is.
+///
+pub fn set_one_off_completed(
+    save: &mut WillowTwoPlayerSaveGame,
+    package_id: i32,
+    content_id: i32,
+    challenge: usize,
+    completed: bool,
+) {
+    if !completed && !is_one_off_completed(save, package_id, content_id, challenge) {
+        return;
+    }
+    let index = match save
+        .OneOffLevelChallengeCompletion
+        .iter()
+        .position(|data| data.PackageId() == package_id && data.ContentId() == content_id)
+    {
+        Some(index) => index,
+        None => {
+            let mut data = OneOffLevelChallengeData::new();
+            data.set_PackageId(package_id);
+            data.set_ContentId(content_id);
+            save.OneOffLevelChallengeCompletion.push(data);
+            save.OneOffLevelChallengeCompletion.len() - 1
+        }
+    };
+    let completion = &mut save.OneOffLevelChallengeCompletion[index].Completion;
+    if completion.len() <= challenge / 32 {
+        completion.resize(challenge / 32 + 1, 0);
+    }
+    let bit = 1 << (challenge % 32);
+    if completed {
+        completion[challenge / 32] |= bit;
+    } else {
+        completion[challenge / 32] &= !bit;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        challenge_tier, challenges, complete_challenge, is_one_off_completed,
+        level_challenge_unlocks, reset_challenge, set_level_challenge_unlocked,
+        set_one_off_completed, set_stats, stat, stats, ChallengeDefinition, ChallengeError,
+    };
+    use crate::load_test_save;
+
+    #[test]
+    fn stats_test() {
+        let mut save_game = load_test_save();
+        let entries = stats(&save_game).unwrap();
+        assert_eq!(entries.len(), 387);
+        let original = save_game.StatsData().to_vec();
+        set_stats(&mut save_game, &entries);
+        assert_eq!(save_game.StatsData(), &original[..]);
+        assert_eq!(stat(&save_game, 1621).unwrap().value, 76161);
+    }
+
+    #[test]
+    fn challenge_progress_test() {
+        let mut save_game = load_test_save();
+        // Made up for the test, the real stat and goals come from the game data.
+        let name = "GD_Challenges.Dueling.DuelsWon_HatersGonnaHate";
+        let definitions = [ChallengeDefinition {
+            name: name.to_string(),
+            display_name: "Haters Gonna Hate".to_string(),
+            stat_id: 1621,
+            goals: vec![1000, 10000, 50000, 100000, 250000],
+        }];
+
+        let listed = challenges(&save_game, &definitions).unwrap();
+        assert_eq!(listed.len(), 247);
+        assert_eq!(listed[2].name, name);
+        assert_eq!(listed[2].display_name, "Haters Gonna Hate");
+        assert_eq!(listed[2].tier, Some(3));
+        assert_eq!(listed[0].progress, None);
+        assert!(listed[0].display_name.contains(' '));
+
+        assert_eq!(challenge_tier(&save_game, &definitions, name).unwrap(), 3);
+        complete_challenge(&mut save_game, &definitions, name, 5).unwrap();
+        assert_eq!(challenge_tier(&save_game, &definitions, name).unwrap(), 5);
+        assert!(complete_challenge(&mut save_game, &definitions, name, 6).is_err());
+        reset_challenge(&mut save_game, &definitions, name).unwrap();
+        assert_eq!(stat(&save_game, 1621).unwrap().progress(), 0);
+        let undefined = save_game.ChallengeList[0].Challenge().to_string();
+        assert!(matches!(
+            reset_challenge(&mut save_game, &definitions, &undefined),
+            Err(ChallengeError::NoSuchChallenge { .. })
+        ));
+    }
+
+    #[test]
+    fn level_challenges_test() {
+        let mut save_game = load_test_save();
+        let unlocks = level_challenge_unlocks(&save_game);
+        assert_eq!(unlocks.len(), 17);
+        assert_eq!(unlocks[0], (7, 1));
+
+        set_level_challenge_unlocked(&mut save_game, 7, 3, true).unwrap();
+        set_level_challenge_unlocked(&mut save_game, 99, 0, true).unwrap();
+        assert!(set_level_challenge_unlocked(&mut save_game, 7, 12, true).is_err());
+        let unlocks = level_challenge_unlocks(&save_game);
+        assert_eq!(unlocks[0], (7, 9));
+        assert_eq!(unlocks[17], (99, 1));
+
+        assert!(is_one_off_completed(&save_game, 0, 0, 1));
+        assert!(!is_one_off_completed(&save_game, 0, 0, 0));
+        let completions = save_game.OneOffLevelChallengeCompletion.clone();
+        set_one_off_completed(&mut save_game, 0, 0, 40, false);
+        set_one_off_completed(&mut save_game, 5, 5, 0, false);
+        assert_eq!(save_game.OneOffLevelChallengeCompletion, completions);
+        set_one_off_completed(&mut save_game, 0, 0, 1, false);
+        assert!(!is_one_off_completed(&save_game, 0, 0, 1));
+        set_one_off_completed(&mut save_game, 0, 0, 1, true);
+        set_one_off_completed(&mut save_game, 0, 0, 0, true);
+        set_one_off_completed(&mut save_game, 0, 0, 40, false);
+        set_one_off_completed(&mut save_game, 1, 2, 33, true);
+        assert!(is_one_off_completed(&save_game, 0, 0, 0));
+        assert!(is_one_off_completed(&save_game, 1, 2, 33));
+        assert_eq!(
+            save_game.OneOffLevelChallengeCompletion[1].Completion,
+            vec![0, 2]
+        );
+    }
+}
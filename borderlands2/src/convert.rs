@@ -0,0 +1,524 @@
+use crate::assets::{self, AssetKind, AssetReference};
+use crate::serial::{
+    asset_kinds, decode_serial, encode_serial, next_seed, InventorySerial, SerialError,
+    SERIAL_VERSION,
+};
+use crate::ItemData::ItemData;
+use crate::PackedItemData::PackedItemData;
+use crate::PackedWeaponData::PackedWeaponData;
+use crate::PlayerMark::PlayerMark;
+use crate::WeaponData::WeaponData;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+use protobuf::Enum;
+
+///
+/// Highest level that fits into the 7 bits a serial stores it in.
+///
+pub const MAX_SERIAL_LEVEL: i32 = 127;
+
+#[derive(Debug)]
+pub enum ConvertError {
+    UnknownAsset { msg: String },
+    InvalidSerial { msg: String },
+}
+
+impl std::fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConvertError::UnknownAsset { msg } => write!(f, "UnknownAsset: {}", msg),
+            ConvertError::InvalidSerial { msg } => write!(f, "InvalidSerial: {}", msg),
+        }
+    }
+}
+
+impl From<SerialError> for ConvertError {
+    fn from(err: SerialError) -> Self {
+        ConvertError::InvalidSerial {
+            msg: err.to_string(),
+        }
+    }
+}
+
+///
+/// The asset paths stored in an unpacked weapon or item.
+///
+/// The parts are in serial order, see `PartSlot::WEAPON` and `PartSlot::ITEM`. Weapons keep
+/// the game stage of the serial in `Unknown16`. `ItemData` has no field for it, so items use
+/// their manufacturer grade.
+///
+struct GearNames {
+    is_weapon: bool,
+    item_type: String,
+    balance: String,
+    manufacturer: String,
+    manufacturer_grade_index: i32,
+    game_stage: i32,
+    parts: [String; 11],
+}
+
+fn weapon_names(weapon: &WeaponData) -> GearNames {
+    GearNames {
+        is_weapon: true,
+        item_type: weapon.Type().to_string(),
+        balance: weapon.Balance().to_string(),
+        manufacturer: weapon.Manufacturer().to_string(),
+        manufacturer_grade_index: weapon.ManufacturerGradeIndex(),
+        game_stage: weapon.Unknown16(),
+        parts: [
+            weapon.BodyPart().to_string(),
+            weapon.GripPart().to_string(),
+            weapon.BarrelPart().to_string(),
+            weapon.SightPart().to_string(),
+            weapon.StockPart().to_string(),
+            weapon.ElementalPart().to_string(),
+            weapon.Accessory1Part().to_string(),
+            weapon.Accessory2Part().to_string(),
+            weapon.MaterialPart().to_string(),
+            weapon.PrefixPart().to_string(),
+            weapon.TitlePart().to_string(),
+        ],
+    }
+}
+
+fn item_names(item: &ItemData) -> GearNames {
+    GearNames {
+        is_weapon: false,
+        item_type: item.Type().to_string(),
+        balance: item.Balance().to_string(),
+        manufacturer: item.Manufacturer().to_string(),
+        manufacturer_grade_index: item.ManufacturerGradeIndex(),
+        game_stage: item.ManufacturerGradeIndex(),
+        parts: [
+            item.AlphaPart().to_string(),
+            item.BetaPart().to_string(),
+            item.GammaPart().to_string(),
+            item.DeltaPart().to_string(),
+            item.EpislonPart().to_string(),
+            item.ZetaPart().to_string(),
+            item.EtaPart().to_string(),
+            item.ThetaPart().to_string(),
+            item.MaterialPart().to_string(),
+            item.PrefixPart().to_string(),
+            item.TitlePart().to_string(),
+        ],
+    }
+}
+
+///
+/// Packs the asset paths into a serial encrypted with the given seed.
+///
+fn names_to_serial(names: &GearNames, seed: i32) -> Result<Vec<u8>, ConvertError> {
+    let (type_kind, balance_kind, part_kind) = asset_kinds(names.is_weapon);
+    for level in [names.manufacturer_grade_index, names.game_stage] {
+        if !(0..=MAX_SERIAL_LEVEL).contains(&level) {
+            return Err(ConvertError::InvalidSerial {
+                msg: format!("Level {} is outside of 0 to {}!", level, MAX_SERIAL_LEVEL),
+            });
+        }
+    }
+
+    let mut set_id = 0;
+    let mut reference = |kind: AssetKind, name: &str| -> Result<AssetReference, ConvertError> {
+        let (asset_set_id, reference) = match assets::find_asset(kind, name) {
+            Some(found) => found,
+            None => {
+                return Err(ConvertError::UnknownAsset {
+                    msg: format!("{} is not in the asset tables!", name),
+                })
+            }
+        };
+        if asset_set_id != 0 {
+            if set_id != 0 && set_id != asset_set_id {
+                return Err(ConvertError::UnknownAsset {
+                    msg: format!("{} is from a different DLC than the other parts!", name),
+                });
+            }
+            set_id = asset_set_id;
+        }
+        Ok(reference)
+    };
+
+    let item_type = reference(type_kind, &names.item_type)?;
+    let balance = reference(balance_kind, &names.balance)?;
+    let manufacturer = if names.manufacturer.is_empty() {
+        None
+    } else {
+        Some(reference(AssetKind::Manufacturer, &names.manufacturer)?)
+    };
+    let mut parts = Vec::new();
+    for part in &names.parts {
+        parts.push(if part.is_empty() {
+            None
+        } else {
+            Some(reference(part_kind, part)?)
+        });
+    }
+    // The game leaves out trailing parts that are not set.
+    while parts.last() == Some(&None) {
+        parts.pop();
+    }
+
+    Ok(encode_serial(&InventorySerial {
+        is_weapon: names.is_weapon,
+        version: SERIAL_VERSION,
+        seed,
+        set_id,
+        item_type,
+        balance,
+        manufacturer,
+        manufacturer_grade_index: names.manufacturer_grade_index as u8,
+        game_stage: names.game_stage as u8,
+        parts,
+        trailing: Vec::new(),
+    }))
+}
+
+///
+/// Resolves the asset references of a serial to their paths.
+///
+fn serial_to_names(serial: &[u8]) -> Result<GearNames, ConvertError> {
+    let serial = decode_serial(serial)?;
+    let (type_kind, balance_kind, part_kind) = asset_kinds(serial.is_weapon);
+    if !serial.trailing.is_empty() {
+        return Err(ConvertError::InvalidSerial {
+            msg: "Serial holds data the unpacked form can not store!".to_string(),
+        });
+    }
+
+    let name = |kind: AssetKind, reference: &AssetReference| -> Result<String, ConvertError> {
+        match assets::asset_name(serial.set_id, kind, reference) {
+            Some(name) => Ok(name),
+            None => Err(ConvertError::UnknownAsset {
+                msg: format!(
+                    "No {:?} asset for {:?} in set {}!",
+                    kind, reference, serial.set_id
+                ),
+            }),
+        }
+    };
+
+    let mut parts: [String; 11] = Default::default();
+    for (index, part) in serial.parts.iter().enumerate() {
+        if let Some(part) = part {
+            parts[index] = name(part_kind, part)?;
+        }
+    }
+
+    Ok(GearNames {
+        is_weapon: serial.is_weapon,
+        item_type: name(type_kind, &serial.item_type)?,
+        balance: name(balance_kind, &serial.balance)?,
+        manufacturer: match &serial.manufacturer {
+            Some(manufacturer) => name(AssetKind::Manufacturer, manufacturer)?,
+            None => String::new(),
+        },
+        manufacturer_grade_index: serial.manufacturer_grade_index as i32,
+        game_stage: serial.game_stage as i32,
+        parts,
+    })
+}
+
+///
+/// Returns the packed serial of an unpacked weapon.
+///
+pub fn weapon_serial(weapon: &WeaponData, seed: i32) -> Result<Vec<u8>, ConvertError> {
+    names_to_serial(&weapon_names(weapon), seed)
+}
+
+///
+/// Returns the packed serial of an unpacked item.
+///
+pub fn item_serial(item: &ItemData, seed: i32) -> Result<Vec<u8>, ConvertError> {
+    names_to_serial(&item_names(item), seed)
+}
+
+///
+/// Converts an unpacked weapon to its packed form. The serial is encrypted with `seed`.
+///
+pub fn pack_weapon(weapon: &WeaponData, seed: i32) -> Result<PackedWeaponData, ConvertError> {
+    let mut packed = PackedWeaponData::new();
+    packed.set_InventorySerialNumber(weapon_serial(weapon, seed)?);
+    packed.set_QuickSlot(weapon.QuickSlot());
+    packed.set_Mark(weapon.Mark());
+    Ok(packed)
+}
+
+///
+/// Converts a packed weapon to its unpacked form.
+///
+/// The seed of the serial is not part of the unpacked form, see `serial_seed`.
+///
+pub fn unpack_weapon(packed: &PackedWeaponData) -> Result<WeaponData, ConvertError> {
+    let names = serial_to_names(packed.InventorySerialNumber())?;
+    if !names.is_weapon {
+        return Err(ConvertError::InvalidSerial {
+            msg: "Serial of a packed weapon encodes an item!".to_string(),
+        });
+    }
+    let [body, grip, barrel, sight, stock, elemental, accessory1, accessory2, material, prefix, title] =
+        names.parts;
+
+    let mut weapon = WeaponData::new();
+    weapon.set_Balance(names.balance);
+    weapon.set_Manufacturer(names.manufacturer);
+    weapon.set_Type(names.item_type);
+    weapon.set_BodyPart(body);
+    weapon.set_GripPart(grip);
+    weapon.set_BarrelPart(barrel);
+    weapon.set_SightPart(sight);
+    weapon.set_StockPart(stock);
+    weapon.set_Unknown9(String::new());
+    weapon.set_Unknown10(String::new());
+    weapon.set_Unknown11(String::new());
+    weapon.set_Unknown12(String::new());
+    weapon.set_MaterialPart(material);
+    weapon.set_PrefixPart(prefix);
+    weapon.set_TitlePart(title);
+    weapon.set_Unknown16(names.game_stage);
+    weapon.set_ManufacturerGradeIndex(names.manufacturer_grade_index);
+    weapon.set_QuickSlot(packed.QuickSlot());
+    weapon.set_Mark(packed.Mark());
+    weapon.set_ElementalPart(elemental);
+    weapon.set_Accessory1Part(accessory1);
+    weapon.set_Accessory2Part(accessory2);
+    Ok(weapon)
+}
+
+///
+/// Converts an unpacked item to its packed form. The serial is encrypted with `seed`.
+///
+pub fn pack_item(item: &ItemData, seed: i32) -> Result<PackedItemData, ConvertError> {
+    let mut packed = PackedItemData::new();
+    packed.set_InventorySerialNumber(item_serial(item, seed)?);
+    packed.set_Quantity(item.Quantity());
+    packed.set_Equipped(item.Equipped());
+    packed.set_Mark(item.Mark().value());
+    Ok(packed)
+}
+
+///
+/// Converts a packed item to its unpacked form.
+///
+pub fn unpack_item(packed: &PackedItemData) -> Result<ItemData, ConvertError> {
+    let names = serial_to_names(packed.InventorySerialNumber())?;
+    if names.is_weapon {
+        return Err(ConvertError::InvalidSerial {
+            msg: "Serial of a packed item encodes a weapon!".to_string(),
+        });
+    }
+    if names.game_stage != names.manufacturer_grade_index {
+        return Err(ConvertError::InvalidSerial {
+            msg: format!(
+                "Game stage {} differs from grade {}, ItemData can not store it!",
+                names.game_stage, names.manufacturer_grade_index
+            ),
+        });
+    }
+    let [alpha, beta, gamma, delta, epsilon, zeta, eta, theta, material, prefix, title] =
+        names.parts;
+
+    let mut item = ItemData::new();
+    item.set_Balance(names.balance);
+    item.set_Type(names.item_type);
+    item.set_AlphaPart(alpha);
+    item.set_BetaPart(beta);
+    item.set_GammaPart(gamma);
+    item.set_DeltaPart(delta);
+    item.set_EpislonPart(epsilon);
+    item.set_ZetaPart(zeta);
+    item.set_EtaPart(eta);
+    item.set_ThetaPart(theta);
+    item.set_MaterialPart(material);
+    item.set_Manufacturer(names.manufacturer);
+    item.set_PrefixPart(prefix);
+    item.set_TitlePart(title);
+    item.set_Quantity(packed.Quantity());
+    item.set_ManufacturerGradeIndex(names.manufacturer_grade_index);
+    item.set_Equipped(packed.Equipped());
+    item.set_Mark(PlayerMark::from_i32(packed.Mark()).unwrap_or(PlayerMark::Standard));
+    Ok(item)
+}
+
+///
+/// Returns the seed a serial is encrypted with.
+///
+pub fn serial_seed(serial: &[u8]) -> Result<i32, ConvertError> {
+    Ok(decode_serial(serial)?.seed)
+}
+
+///
+/// Converts all unpacked weapons and items of a save to the packed form.
+///
+/// Every serial gets its own seed, derived from `seed` with `next_seed`. Gear that can not be
+/// converted stays unpacked, the errors are returned.
+///
+pub fn pack_all(save: &mut WillowTwoPlayerSaveGame, seed: i32) -> Vec<ConvertError> {
+    let mut errors = Vec::new();
+    let mut seed = seed;
+
+    let mut remaining_weapons = Vec::new();
+    for weapon in save.WeaponData.drain(..) {
+        seed = next_seed(seed);
+        match pack_weapon(&weapon, seed) {
+            Ok(packed) => save.PackedWeaponData.push(packed),
+            Err(err) => {
+                errors.push(err);
+                remaining_weapons.push(weapon);
+            }
+        }
+    }
+    save.WeaponData = remaining_weapons;
+
+    let mut remaining_items = Vec::new();
+    for item in save.ItemData.drain(..) {
+        seed = next_seed(seed);
+        match pack_item(&item, seed) {
+            Ok(packed) => save.PackedItemData.push(packed),
+            Err(err) => {
+                errors.push(err);
+                remaining_items.push(item);
+            }
+        }
+    }
+    save.ItemData = remaining_items;
+
+    errors
+}
+
+///
+/// Converts all packed weapons and items of a save to the unpacked form.
+///
+/// Gear that can not be converted, for example because its assets are not in the
+/// asset tables, stays packed and the errors are returned.
+///
+pub fn unpack_all(save: &mut WillowTwoPlayerSaveGame) -> Vec<ConvertError> {
+    let mut errors = Vec::new();
+
+    let mut remaining_weapons = Vec::new();
+    for packed in save.PackedWeaponData.drain(..) {
+        match unpack_weapon(&packed) {
+            Ok(weapon) => save.WeaponData.push(weapon),
+            Err(err) => {
+                errors.push(err);
+                remaining_weapons.push(packed);
+            }
+        }
+    }
+    save.PackedWeaponData = remaining_weapons;
+
+    let mut remaining_items = Vec::new();
+    for packed in save.PackedItemData.drain(..) {
+        match unpack_item(&packed) {
+            Ok(item) => save.ItemData.push(item),
+            Err(err) => {
+                errors.push(err);
+                remaining_items.push(packed);
+            }
+        }
+    }
+    save.PackedItemData = remaining_items;
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        pack_all, pack_item, pack_weapon, serial_seed, unpack_all, unpack_item, unpack_weapon,
+    };
+    use crate::assets;
+    use crate::builder::GearBuilder;
+    use crate::load_test_save;
+    use crate::serial::{decode_serial, encode_serial};
+    use crate::PlayerMark::PlayerMark;
+    use crate::QuickWeaponSlot::QuickWeaponSlot;
+
+    #[test]
+    fn weapon_round_trip_test() {
+        let mut builder =
+            GearBuilder::new("GD_Weap_SniperRifles.A_Weapons.Sniper_Hyperion_3_Rare").unwrap();
+        builder.level(50).unwrap();
+        let mut weapon = builder.build_weapon().unwrap();
+        weapon.set_QuickSlot(QuickWeaponSlot::Left);
+        weapon.set_Mark(PlayerMark::Favorite);
+
+        let packed = pack_weapon(&weapon, -1_234_567).unwrap();
+        assert_eq!(packed.QuickSlot(), QuickWeaponSlot::Left);
+        assert_eq!(
+            serial_seed(packed.InventorySerialNumber()).unwrap(),
+            -1_234_567
+        );
+        assert_eq!(unpack_weapon(&packed).unwrap(), weapon);
+        assert!(unpack_item(&Default::default()).is_err());
+    }
+
+    #[test]
+    fn item_round_trip_test() {
+        for balance in assets::balances().iter().filter(|b| !b.is_weapon) {
+            let mut builder = GearBuilder::new(balance.name).unwrap();
+            builder.level(23).unwrap();
+            let mut item = builder.build_item().unwrap();
+            item.set_Equipped(true);
+            item.set_Quantity(3);
+
+            let packed = pack_item(&item, 42).unwrap();
+            assert!(packed.Equipped());
+            assert_eq!(packed.Quantity(), 3);
+            assert_eq!(unpack_item(&packed).unwrap(), item);
+            assert_eq!(
+                pack_item(&unpack_item(&packed).unwrap(), 42).unwrap(),
+                packed
+            );
+        }
+    }
+
+    #[test]
+    fn game_stage_round_trip_test() {
+        // Like the item in Save0001 that has grade 0 and game stage 1.
+        let mut builder =
+            GearBuilder::new("GD_Weap_Pistol.A_Weapons.Pistol_Jakobs_3_Rare").unwrap();
+        let mut weapon = builder.level(0).unwrap().build_weapon().unwrap();
+        weapon.set_Unknown16(1);
+        let packed = pack_weapon(&weapon, 99).unwrap();
+        let serial = decode_serial(packed.InventorySerialNumber()).unwrap();
+        assert_eq!(serial.manufacturer_grade_index, 0);
+        assert_eq!(serial.game_stage, 1);
+        assert_eq!(unpack_weapon(&packed).unwrap(), weapon);
+        weapon.set_Unknown16(128);
+        assert!(pack_weapon(&weapon, 99).is_err());
+
+        let builder =
+            GearBuilder::new("GD_ItemGrades.Shields.ItemGrade_Gear_Shield_Standard_03_Rare");
+        let mut packed = pack_item(&builder.unwrap().build_item().unwrap(), 99).unwrap();
+        let mut serial = decode_serial(packed.InventorySerialNumber()).unwrap();
+        serial.game_stage += 1;
+        packed.set_InventorySerialNumber(encode_serial(&serial));
+        assert!(unpack_item(&packed).is_err());
+    }
+
+    #[test]
+    fn unpack_all_test() {
+        let mut save_game = load_test_save();
+        let original = save_game.clone();
+
+        let mut builder = GearBuilder::new("GD_Weap_Shotgun.A_Weapons.SG_Torgue_3_Rare").unwrap();
+        let weapon = builder.level(30).unwrap().build_weapon().unwrap();
+        save_game.WeaponData.push(weapon.clone());
+        save_game.WeaponData.push(weapon);
+        assert!(pack_all(&mut save_game, 7).is_empty());
+        assert!(save_game.WeaponData.is_empty());
+        let packed = &save_game.PackedWeaponData[original.PackedWeaponData.len()..];
+        assert_eq!(packed.len(), 2);
+        assert_ne!(
+            serial_seed(packed[0].InventorySerialNumber()).unwrap(),
+            serial_seed(packed[1].InventorySerialNumber()).unwrap()
+        );
+
+        // Gear the asset tables can not resolve stays packed byte for byte.
+        unpack_all(&mut save_game);
+        assert_eq!(save_game.WeaponData.len(), 2);
+        assert_eq!(save_game.PackedWeaponData, original.PackedWeaponData);
+        assert_eq!(save_game.PackedItemData, original.PackedItemData);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+///
+/// Currencies stored in `CurrencyOnHand`, the discriminant is the position in the list.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Currency {
+    Money = 0,
+    Eridium = 1,
+    SeraphCrystals = 2,
+    TorgueTokens = 4,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 4] = [
+        Currency::Money,
+        Currency::Eridium,
+        Currency::SeraphCrystals,
+        Currency::TorgueTokens,
+    ];
+
+    ///
+    /// Returns the position of the currency in `CurrencyOnHand`.
+    ///
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    ///
+    /// Returns the highest amount the game keeps.
+    ///
+    /// Money overflows into negative amounts past the int32 limit, the others are capped by
+    /// the game.
+    ///
+    pub fn cap(self) -> i32 {
+        match self {
+            Currency::Money => i32::MAX,
+            Currency::Eridium => 500,
+            Currency::SeraphCrystals => 999,
+            Currency::TorgueTokens => 999,
+        }
+    }
+}
+
+///
+/// Returns the amount of a currency, missing entries count as 0.
+///
+pub fn currency(save: &WillowTwoPlayerSaveGame, currency: Currency) -> i32 {
+    save.CurrencyOnHand
+        .get(currency.index())
+        .copied()
+        .unwrap_or(0)
+}
+
+///
+/// Sets the amount of a currency clamped to 0 and its cap, returns the stored amount.
+///
+/// `CurrencyOnHand` is extended with zeros if it is too short.
+///
+pub fn set_currency(save: &mut WillowTwoPlayerSaveGame, currency: Currency, amount: i32) -> i32 {
+    let amount = amount.clamp(0, currency.cap());
+    if save.CurrencyOnHand.len() <= currency.index() {
+        save.CurrencyOnHand.resize(currency.index() + 1, 0);
+    }
+    save.CurrencyOnHand[currency.index()] = amount;
+    amount
+}
+
+///
+/// Adds to or, with a negative amount, takes from a currency, see `set_currency`.
+///
+pub fn add_currency(save: &mut WillowTwoPlayerSaveGame, currency: Currency, amount: i32) -> i32 {
+    let total = self::currency(save, currency).saturating_add(amount);
+    set_currency(save, currency, total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_currency, currency, set_currency, Currency};
+    use crate::load_test_save;
+
+    #[test]
+    fn currency_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(currency(&save_game, Currency::Money), 11495519);
+        assert_eq!(currency(&save_game, Currency::Eridium), 99);
+        assert_eq!(currency(&save_game, Currency::TorgueTokens), 120);
+
+        assert_eq!(set_currency(&mut save_game, Currency::Eridium, 1000), 500);
+        assert_eq!(
+            add_currency(&mut save_game, Currency::Money, i32::MAX),
+            i32::MAX
+        );
+        assert_eq!(
+            add_currency(&mut save_game, Currency::SeraphCrystals, -500),
+            0
+        );
+        assert_eq!(save_game.CurrencyOnHand[..3], [i32::MAX, 500, 0]);
+    }
+
+    #[test]
+    fn grow_currency_test() {
+        let mut save_game = load_test_save();
+        save_game.CurrencyOnHand.clear();
+        assert_eq!(currency(&save_game, Currency::SeraphCrystals), 0);
+        set_currency(&mut save_game, Currency::TorgueTokens, 42);
+        assert_eq!(save_game.CurrencyOnHand, vec![0, 0, 0, 0, 42]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use crate::assets::AssetReference;
+use crate::convert;
+use crate::equipment::GearIndex;
+use crate::serial::{decode_serial, encode_serial, InventorySerial};
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+///
+/// Where a weapon or item is stored inside a save.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Storage {
+    Backpack(GearIndex),
+    Bank(usize),
+}
+
+///
+/// Location of a weapon or item across several saves.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GearLocation {
+    /// Index of the save in the list passed to `find_duplicates`.
+    pub save: usize,
+    pub storage: Storage,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DuplicateKind {
+    /// The serials are equal once they are encrypted with the same seed.
+    Identical,
+    /// The balance and the parts are equal, but the serials differ, e.g. in level.
+    Similar,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    pub kind: DuplicateKind,
+    pub locations: Vec<GearLocation>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DuplicateReport {
+    /// Groups of duplicates in the order their first member was found.
+    pub groups: Vec<DuplicateGroup>,
+    /// Gear that could not be decoded or packed and was not compared.
+    pub unreadable: Vec<GearLocation>,
+}
+
+/// The balance and parts a `DuplicateKind::Similar` group shares.
+type SimilarKey = (bool, u8, AssetReference, Vec<Option<AssetReference>>);
+
+///
+/// Returns the serial of every weapon and item of a save together with its storage.
+///
+/// Unpacked gear is packed with a zero seed. Packed items with a quantity below one are
+/// placeholders kept by the game and are left out.
+///
+fn gear_serials(save: &WillowTwoPlayerSaveGame) -> Vec<(Storage, Option<Vec<u8>>)> {
+    let mut serials = Vec::new();
+    for (index, weapon) in save.WeaponData.iter().enumerate() {
+        serials.push((
+            Storage::Backpack(GearIndex::Weapon(index)),
+            convert::weapon_serial(weapon, 0).ok(),
+        ));
+    }
+    for (index, weapon) in save.PackedWeaponData.iter().enumerate() {
+        serials.push((
+            Storage::Backpack(GearIndex::PackedWeapon(index)),
+            Some(weapon.InventorySerialNumber().to_vec()),
+        ));
+    }
+    for (index, item) in save.ItemData.iter().enumerate() {
+        serials.push((
+            Storage::Backpack(GearIndex::Item(index)),
+            convert::item_serial(item, 0).ok(),
+        ));
+    }
+    for (index, item) in save.PackedItemData.iter().enumerate() {
+        if item.Quantity() > 0 {
+            serials.push((
+                Storage::Backpack(GearIndex::PackedItem(index)),
+                Some(item.InventorySerialNumber().to_vec()),
+            ));
+        }
+    }
+    for (index, slot) in save.BankSlots.iter().enumerate() {
+        serials.push((
+            Storage::Bank(index),
+            Some(slot.InventorySerialNumber().to_vec()),
+        ));
+    }
+    serials
+}
+
+///
+/// Finds identical and similar gear in the backpacks and banks of the given saves.
+///
+/// Serials are compared with their seed set to zero, so re-encrypted copies are found as
+/// well. Every group of identical gear is also part of a similar group if a differing copy
+/// with the same balance and parts exists.
+///
+pub fn find_duplicates(saves: &[&WillowTwoPlayerSaveGame]) -> DuplicateReport {
+    let mut report = DuplicateReport::default();
+    let mut identical: Vec<(InventorySerial, Vec<GearLocation>)> = Vec::new();
+    let mut identical_index: HashMap<Vec<u8>, usize> = HashMap::new();
+
+    for (save_index, save) in saves.iter().enumerate() {
+        for (storage, serial) in gear_serials(save) {
+            let location = GearLocation {
+                save: save_index,
+                storage,
+            };
+            let decoded = match serial.map(|serial| decode_serial(&serial)) {
+                Some(Ok(decoded)) => decoded,
+                _ => {
+                    report.unreadable.push(location);
+                    continue;
+                }
+            };
+            let mut normalized = decoded;
+            normalized.seed = 0;
+            let key = encode_serial(&normalized);
+            match identical_index.get(&key) {
+                Some(index) => identical[*index].1.push(location),
+                None => {
+                    identical_index.insert(key, identical.len());
+                    identical.push((normalized, vec![location]));
+                }
+            }
+        }
+    }
+
+    let mut similar: Vec<(SimilarKey, Vec<usize>)> = Vec::new();
+    let mut similar_index: HashMap<SimilarKey, usize> = HashMap::new();
+    for (index, (serial, _)) in identical.iter().enumerate() {
+        let key = (
+            serial.is_weapon,
+            serial.set_id,
+            serial.balance,
+            serial.parts.clone(),
+        );
+        match similar_index.get(&key) {
+            Some(group) => similar[*group].1.push(index),
+            None => {
+                similar_index.insert(key.clone(), similar.len());
+                similar.push((key, vec![index]));
+            }
+        }
+    }
+
+    for (_, members) in similar {
+        for member in &members {
+            let locations = &identical[*member].1;
+            if locations.len() > 1 {
+                report.groups.push(DuplicateGroup {
+                    kind: DuplicateKind::Identical,
+                    locations: locations.clone(),
+                });
+            }
+        }
+        if members.len() > 1 {
+            report.groups.push(DuplicateGroup {
+                kind: DuplicateKind::Similar,
+                locations: members
+                    .iter()
+                    .flat_map(|member| identical[*member].1.iter().copied())
+                    .collect(),
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_duplicates, DuplicateKind, GearLocation, Storage};
+    use crate::equipment::GearIndex;
+    use crate::load_test_save;
+    use crate::serial::{decode_serial, encode_serial, reseed_serial};
+    use crate::BankSlot::BankSlot;
+
+    #[test]
+    fn identical_duplicates_test() {
+        let mut save_game = load_test_save();
+        let report = find_duplicates(&[&save_game]);
+        assert!(report.groups.is_empty());
+        assert!(report.unreadable.is_empty());
+
+        let serial = save_game.PackedWeaponData[3].InventorySerialNumber();
+        let mut slot = BankSlot::new();
+        slot.set_InventorySerialNumber(reseed_serial(serial, 0x0bad_5eed).unwrap());
+        save_game.BankSlots.push(slot);
+
+        let report = find_duplicates(&[&save_game]);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].kind, DuplicateKind::Identical);
+        assert_eq!(
+            report.groups[0].locations,
+            vec![
+                GearLocation {
+                    save: 0,
+                    storage: Storage::Backpack(GearIndex::PackedWeapon(3)),
+                },
+                GearLocation {
+                    save: 0,
+                    storage: Storage::Bank(10),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn similar_duplicates_test() {
+        let save_game = load_test_save();
+        let mut other_save_game = load_test_save();
+        other_save_game.PackedWeaponData.clear();
+        other_save_game.PackedItemData.clear();
+        other_save_game.BankSlots.truncate(1);
+
+        let mut serial =
+            decode_serial(other_save_game.BankSlots[0].InventorySerialNumber()).unwrap();
+        serial.game_stage += 1;
+        other_save_game.BankSlots[0].set_InventorySerialNumber(encode_serial(&serial));
+
+        let report = find_duplicates(&[&save_game, &other_save_game]);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].kind, DuplicateKind::Similar);
+        assert_eq!(
+            report.groups[0].locations,
+            vec![
+                GearLocation {
+                    save: 0,
+                    storage: Storage::Bank(0),
+                },
+                GearLocation {
+                    save: 1,
+                    storage: Storage::Bank(0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicates_across_saves_test() {
+        let save_game = load_test_save();
+        let other_save_game = load_test_save();
+
+        let report = find_duplicates(&[&save_game, &other_save_game]);
+        assert!(report.unreadable.is_empty());
+        // Every weapon and item except the four placeholder items is found in both saves.
+        assert_eq!(report.groups.len(), 45);
+        assert!(report.groups.iter().all(|group| {
+            group.kind == DuplicateKind::Identical
+                && group.locations.len() == 2
+                && group.locations[0].save == 0
+                && group.locations[1].save == 1
+                && group.locations[0].storage == group.locations[1].storage
+        }));
+        assert!(report.groups.iter().any(|group| group.locations[0]
+            == GearLocation {
+                save: 0,
+                storage: Storage::Backpack(GearIndex::PackedItem(13)),
+            }));
+    }
+}
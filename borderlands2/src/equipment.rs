@@ -0,0 +1,483 @@
+use std::collections::HashMap;
+
+use crate::assets::{self, ItemCategory};
+use crate::serial::{asset_kinds, decode_serial};
+use crate::QuickWeaponSlot::QuickWeaponSlot;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+use protobuf::Enum;
+
+#[derive(Debug)]
+pub enum EquipmentError {
+    NoSuchGear { msg: String },
+    InvalidSlot { msg: String },
+    UnknownCategory { msg: String },
+}
+
+impl std::fmt::Display for EquipmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EquipmentError::NoSuchGear { msg } => write!(f, "NoSuchGear: {}", msg),
+            EquipmentError::InvalidSlot { msg } => write!(f, "InvalidSlot: {}", msg),
+            EquipmentError::UnknownCategory { msg } => write!(f, "UnknownCategory: {}", msg),
+        }
+    }
+}
+
+///
+/// Position of a weapon or item in one of the gear lists of a save.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GearIndex {
+    /// Index into `WeaponData`.
+    Weapon(usize),
+    /// Index into `PackedWeaponData`.
+    PackedWeapon(usize),
+    /// Index into `ItemData`.
+    Item(usize),
+    /// Index into `PackedItemData`.
+    PackedItem(usize),
+}
+
+///
+/// An inconsistency in the equipped gear of a save.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EquipmentIssue {
+    /// More than one weapon is assigned to the same quick slot.
+    SharedQuickSlot {
+        slot: QuickWeaponSlot,
+        weapons: Vec<GearIndex>,
+    },
+    /// A weapon is assigned to a quick slot above `WeaponReadyMax`.
+    LockedQuickSlot {
+        slot: QuickWeaponSlot,
+        weapon: GearIndex,
+    },
+    /// More than one item of the same category is equipped.
+    SharedCategory {
+        category: ItemCategory,
+        items: Vec<GearIndex>,
+    },
+    /// An item is equipped whose category can not be determined, so it can not be checked.
+    UnknownCategory { item: GearIndex },
+}
+
+fn no_such_gear(gear: GearIndex) -> EquipmentError {
+    EquipmentError::NoSuchGear {
+        msg: format!("{:?} does not exist!", gear),
+    }
+}
+
+///
+/// Returns all weapons of the save, unpacked weapons first.
+///
+pub fn weapons(save: &WillowTwoPlayerSaveGame) -> Vec<GearIndex> {
+    (0..save.WeaponData.len())
+        .map(GearIndex::Weapon)
+        .chain((0..save.PackedWeaponData.len()).map(GearIndex::PackedWeapon))
+        .collect()
+}
+
+///
+/// Returns all items of the save, unpacked items first.
+///
+pub fn items(save: &WillowTwoPlayerSaveGame) -> Vec<GearIndex> {
+    (0..save.ItemData.len())
+        .map(GearIndex::Item)
+        .chain((0..save.PackedItemData.len()).map(GearIndex::PackedItem))
+        .collect()
+}
+
+///
+/// Returns the quick slot of a weapon.
+///
+pub fn quick_slot(save: &WillowTwoPlayerSaveGame, weapon: GearIndex) -> Option<QuickWeaponSlot> {
+    match weapon {
+        GearIndex::Weapon(index) => save.WeaponData.get(index).map(|w| w.QuickSlot()),
+        GearIndex::PackedWeapon(index) => save.PackedWeaponData.get(index).map(|w| w.QuickSlot()),
+        _ => None,
+    }
+}
+
+fn set_quick_slot(
+    save: &mut WillowTwoPlayerSaveGame,
+    weapon: GearIndex,
+    slot: QuickWeaponSlot,
+) -> Result<(), EquipmentError> {
+    match weapon {
+        GearIndex::Weapon(index) => match save.WeaponData.get_mut(index) {
+            Some(weapon) => weapon.set_QuickSlot(slot),
+            None => return Err(no_such_gear(weapon)),
+        },
+        GearIndex::PackedWeapon(index) => match save.PackedWeaponData.get_mut(index) {
+            Some(weapon) => weapon.set_QuickSlot(slot),
+            None => return Err(no_such_gear(weapon)),
+        },
+        _ => {
+            return Err(EquipmentError::NoSuchGear {
+                msg: format!("{:?} is not a weapon!", weapon),
+            })
+        }
+    }
+    Ok(())
+}
+
+///
+/// Returns the weapon in a quick slot. If several weapons share the slot the first is returned.
+///
+pub fn weapon_in_slot(save: &WillowTwoPlayerSaveGame, slot: QuickWeaponSlot) -> Option<GearIndex> {
+    if slot == QuickWeaponSlot::None {
+        return None;
+    }
+    weapons(save)
+        .into_iter()
+        .find(|weapon| quick_slot(save, *weapon) == Some(slot))
+}
+
+///
+/// Returns true if the quick slot is unlocked by `InventorySlotData.WeaponReadyMax`.
+///
+pub fn is_slot_unlocked(save: &WillowTwoPlayerSaveGame, slot: QuickWeaponSlot) -> bool {
+    slot != QuickWeaponSlot::None && slot.value() <= save.InventorySlotData.WeaponReadyMax()
+}
+
+///
+/// Puts a weapon into a quick slot.
+///
+/// A weapon already in the slot is moved to the backpack and returned.
+///
+pub fn equip_weapon(
+    save: &mut WillowTwoPlayerSaveGame,
+    weapon: GearIndex,
+    slot: QuickWeaponSlot,
+) -> Result<Option<GearIndex>, EquipmentError> {
+    if quick_slot(save, weapon).is_none() {
+        return Err(no_such_gear(weapon));
+    }
+    if !is_slot_unlocked(save, slot) {
+        return Err(EquipmentError::InvalidSlot {
+            msg: format!(
+                "Quick slot {:?} is not available with {} weapon slots!",
+                slot,
+                save.InventorySlotData.WeaponReadyMax()
+            ),
+        });
+    }
+
+    let mut displaced = None;
+    for other in weapons(save) {
+        if other != weapon && quick_slot(save, other) == Some(slot) {
+            set_quick_slot(save, other, QuickWeaponSlot::None)?;
+            displaced.get_or_insert(other);
+        }
+    }
+    set_quick_slot(save, weapon, slot)?;
+    Ok(displaced)
+}
+
+///
+/// Moves a weapon from its quick slot to the backpack.
+///
+pub fn unequip_weapon(
+    save: &mut WillowTwoPlayerSaveGame,
+    weapon: GearIndex,
+) -> Result<(), EquipmentError> {
+    set_quick_slot(save, weapon, QuickWeaponSlot::None)
+}
+
+///
+/// Returns the category of an item.
+///
+/// For packed items the type is looked up in the asset tables, `None` is returned if it is
+/// not found or the serial can not be decoded.
+///
+pub fn item_category_of(save: &WillowTwoPlayerSaveGame, item: GearIndex) -> Option<ItemCategory> {
+    match item {
+        GearIndex::Item(index) => assets::item_category(save.ItemData.get(index)?.Type()),
+        GearIndex::PackedItem(index) => {
+            let serial =
+                decode_serial(save.PackedItemData.get(index)?.InventorySerialNumber()).ok()?;
+            let (type_kind, _, _) = asset_kinds(serial.is_weapon);
+            let item_type = assets::asset_name(serial.set_id, type_kind, &serial.item_type)?;
+            assets::item_category(&item_type)
+        }
+        _ => None,
+    }
+}
+
+///
+/// Returns true if the item is equipped.
+///
+pub fn is_equipped(save: &WillowTwoPlayerSaveGame, item: GearIndex) -> bool {
+    match item {
+        GearIndex::Item(index) => save.ItemData.get(index).is_some_and(|i| i.Equipped()),
+        GearIndex::PackedItem(index) => {
+            save.PackedItemData.get(index).is_some_and(|i| i.Equipped())
+        }
+        _ => false,
+    }
+}
+
+fn set_equipped(
+    save: &mut WillowTwoPlayerSaveGame,
+    item: GearIndex,
+    equipped: bool,
+) -> Result<(), EquipmentError> {
+    match item {
+        GearIndex::Item(index) => match save.ItemData.get_mut(index) {
+            Some(item) => item.set_Equipped(equipped),
+            None => return Err(no_such_gear(item)),
+        },
+        GearIndex::PackedItem(index) => match save.PackedItemData.get_mut(index) {
+            Some(item) => item.set_Equipped(equipped),
+            None => return Err(no_such_gear(item)),
+        },
+        _ => {
+            return Err(EquipmentError::NoSuchGear {
+                msg: format!("{:?} is not an item!", item),
+            })
+        }
+    }
+    Ok(())
+}
+
+///
+/// Equips an item.
+///
+/// An equipped item of the same category is moved to the backpack and returned.
+///
+pub fn equip_item(
+    save: &mut WillowTwoPlayerSaveGame,
+    item: GearIndex,
+) -> Result<Option<GearIndex>, EquipmentError> {
+    let category = match item_category_of(save, item) {
+        Some(category) => category,
+        None => {
+            return Err(EquipmentError::UnknownCategory {
+                msg: format!("Could not determine the category of {:?}!", item),
+            })
+        }
+    };
+
+    let mut displaced = None;
+    for other in items(save) {
+        if other != item
+            && is_equipped(save, other)
+            && item_category_of(save, other) == Some(category)
+        {
+            set_equipped(save, other, false)?;
+            displaced.get_or_insert(other);
+        }
+    }
+    set_equipped(save, item, true)?;
+    Ok(displaced)
+}
+
+///
+/// Moves an equipped item to the backpack.
+///
+pub fn unequip_item(
+    save: &mut WillowTwoPlayerSaveGame,
+    item: GearIndex,
+) -> Result<(), EquipmentError> {
+    set_equipped(save, item, false)
+}
+
+///
+/// Reports all inconsistencies in the quick slots and equipped items of a save.
+///
+/// Equipped items whose category can not be determined are reported as `UnknownCategory`.
+///
+pub fn check_equipment(save: &WillowTwoPlayerSaveGame) -> Vec<EquipmentIssue> {
+    let mut issues = Vec::new();
+
+    let mut slots: HashMap<QuickWeaponSlot, Vec<GearIndex>> = HashMap::new();
+    for weapon in weapons(save) {
+        let slot = quick_slot(save, weapon).unwrap_or(QuickWeaponSlot::None);
+        if slot == QuickWeaponSlot::None {
+            continue;
+        }
+        if !is_slot_unlocked(save, slot) {
+            issues.push(EquipmentIssue::LockedQuickSlot { slot, weapon });
+        }
+        slots.entry(slot).or_default().push(weapon);
+    }
+    for slot in QuickWeaponSlot::VALUES {
+        if let Some(weapons) = slots.remove(slot) {
+            if weapons.len() > 1 {
+                issues.push(EquipmentIssue::SharedQuickSlot {
+                    slot: *slot,
+                    weapons,
+                });
+            }
+        }
+    }
+
+    let mut categories: HashMap<ItemCategory, Vec<GearIndex>> = HashMap::new();
+    for item in items(save) {
+        if !is_equipped(save, item) {
+            continue;
+        }
+        match item_category_of(save, item) {
+            Some(category) => categories.entry(category).or_default().push(item),
+            None => issues.push(EquipmentIssue::UnknownCategory { item }),
+        }
+    }
+    for category in [
+        ItemCategory::Shield,
+        ItemCategory::GrenadeMod,
+        ItemCategory::ClassMod,
+        ItemCategory::Relic,
+    ] {
+        if let Some(items) = categories.remove(&category) {
+            if items.len() > 1 {
+                issues.push(EquipmentIssue::SharedCategory { category, items });
+            }
+        }
+    }
+
+    issues
+}
+
+///
+/// Fixes all inconsistencies reported by `check_equipment` and returns them.
+///
+/// Weapons in locked quick slots are moved to the backpack. Of weapons sharing a quick slot
+/// and of equipped items sharing a category only the first stays equipped. Items of an
+/// unknown category stay equipped. The save is only changed if every issue could be fixed.
+///
+pub fn fix_equipment(
+    save: &mut WillowTwoPlayerSaveGame,
+) -> Result<Vec<EquipmentIssue>, EquipmentError> {
+    let issues = check_equipment(save);
+    let mut working = save.clone();
+    for issue in &issues {
+        match issue {
+            EquipmentIssue::LockedQuickSlot { weapon, .. } => {
+                unequip_weapon(&mut working, *weapon)?
+            }
+            EquipmentIssue::SharedQuickSlot { weapons, .. } => weapons[1..]
+                .iter()
+                .try_for_each(|weapon| unequip_weapon(&mut working, *weapon))?,
+            EquipmentIssue::SharedCategory { items, .. } => items[1..]
+                .iter()
+                .try_for_each(|item| unequip_item(&mut working, *item))?,
+            EquipmentIssue::UnknownCategory { .. } => {}
+        }
+    }
+    *save = working;
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_equipment, equip_item, equip_weapon, fix_equipment, is_equipped, item_category_of,
+        items, quick_slot, EquipmentIssue, GearIndex,
+    };
+    use crate::assets::ItemCategory;
+    use crate::builder::GearBuilder;
+    use crate::convert::pack_item;
+    use crate::load_test_save;
+    use crate::QuickWeaponSlot::QuickWeaponSlot;
+
+    const SHIELD: &str = "GD_ItemGrades.Shields.ItemGrade_Gear_Shield_Standard_03_Rare";
+
+    // The asset tables do not cover the equipped items of the test save.
+    fn unknown_items() -> Vec<EquipmentIssue> {
+        [13, 14, 15, 16]
+            .into_iter()
+            .map(|index| EquipmentIssue::UnknownCategory {
+                item: GearIndex::PackedItem(index),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn equip_weapon_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(check_equipment(&save_game), unknown_items());
+
+        let up = GearIndex::PackedWeapon(0);
+        let spare = GearIndex::PackedWeapon(4);
+        assert_eq!(quick_slot(&save_game, up), Some(QuickWeaponSlot::Up));
+        assert_eq!(
+            equip_weapon(&mut save_game, spare, QuickWeaponSlot::Up).unwrap(),
+            Some(up)
+        );
+        assert_eq!(quick_slot(&save_game, up), Some(QuickWeaponSlot::None));
+
+        save_game
+            .InventorySlotData
+            .mut_or_insert_default()
+            .set_WeaponReadyMax(2);
+        assert!(equip_weapon(&mut save_game, up, QuickWeaponSlot::Left).is_err());
+        assert_eq!(fix_equipment(&mut save_game).unwrap().len(), 6);
+        assert_eq!(check_equipment(&save_game), unknown_items());
+    }
+
+    #[test]
+    fn equip_item_test() {
+        let mut save_game = load_test_save();
+        let shield = GearBuilder::new(SHIELD).unwrap().build_item().unwrap();
+        save_game.ItemData.push(shield.clone());
+        save_game.ItemData.push(shield);
+        save_game.ItemData[0].set_Equipped(true);
+        save_game.ItemData[1].set_Equipped(true);
+        assert!(matches!(
+            check_equipment(&save_game)[4..],
+            [EquipmentIssue::SharedCategory { .. }]
+        ));
+
+        assert_eq!(
+            equip_item(&mut save_game, GearIndex::Item(1)).unwrap(),
+            Some(GearIndex::Item(0))
+        );
+        assert!(!is_equipped(&save_game, GearIndex::Item(0)));
+        assert_eq!(check_equipment(&save_game), unknown_items());
+    }
+
+    #[test]
+    fn equip_packed_item_test() {
+        let mut save_game = load_test_save();
+        let equipped: Vec<GearIndex> = items(&save_game)
+            .into_iter()
+            .filter(|item| is_equipped(&save_game, *item))
+            .collect();
+        assert!(equipped
+            .iter()
+            .all(|item| item_category_of(&save_game, *item).is_none()));
+        assert!(equip_item(&mut save_game, GearIndex::PackedItem(0)).is_err());
+        // Items of an unknown category are reported but stay equipped.
+        assert_eq!(fix_equipment(&mut save_game).unwrap(), unknown_items());
+        assert!(equipped.iter().all(|item| is_equipped(&save_game, *item)));
+
+        // An unpacked and a packed shield conflict.
+        let mut shield = GearBuilder::new(SHIELD).unwrap().build_item().unwrap();
+        shield.set_Equipped(true);
+        save_game
+            .PackedItemData
+            .push(pack_item(&shield, 5).unwrap());
+        save_game.ItemData.push(shield);
+        let packed = GearIndex::PackedItem(save_game.PackedItemData.len() - 1);
+        assert_eq!(
+            item_category_of(&save_game, packed),
+            Some(ItemCategory::Shield)
+        );
+        assert_eq!(
+            check_equipment(&save_game)[4..],
+            [EquipmentIssue::SharedCategory {
+                category: ItemCategory::Shield,
+                items: vec![GearIndex::Item(0), packed],
+            }]
+        );
+        assert_eq!(fix_equipment(&mut save_game).unwrap().len(), 5);
+        assert!(!is_equipped(&save_game, packed));
+        assert_eq!(
+            equip_item(&mut save_game, packed).unwrap(),
+            Some(GearIndex::Item(0))
+        );
+        assert_eq!(check_equipment(&save_game), unknown_items());
+    }
+}
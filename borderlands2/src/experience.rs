@@ -0,0 +1,213 @@
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+/// Level a new character starts at.
+pub const MIN_LEVEL: i32 = 1;
+/// First level that grants a skill point.
+pub const FIRST_SKILL_POINT_LEVEL: i32 = 5;
+/// Level cap once all upgrade packs are installed.
+///
+/// Overpower levels can not be edited. The game keeps them outside of `ExpLevel`, most likely
+/// in `DLCExpansionData`, but Save0001 has no such entry to check the storage against.
+pub const MAX_LEVEL: i32 = 80;
+
+#[derive(Debug)]
+pub enum ExperienceError {
+    InvalidLevel { msg: String },
+    InvalidExperience { msg: String },
+    NotEnoughSkillPoints { msg: String },
+}
+
+impl std::fmt::Display for ExperienceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ExperienceError::InvalidLevel { msg } => write!(f, "InvalidLevel: {}", msg),
+            ExperienceError::InvalidExperience { msg } => {
+                write!(f, "InvalidExperience: {}", msg)
+            }
+            ExperienceError::NotEnoughSkillPoints { msg } => {
+                write!(f, "NotEnoughSkillPoints: {}", msg)
+            }
+        }
+    }
+}
+
+///
+/// Level cap raising DLC installed alongside the game.
+///
+/// The save does not record which DLC is installed, so callers have to pass it in.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UpgradePack {
+    /// The base game.
+    None,
+    /// Ultimate Vault Hunter Upgrade Pack.
+    UltimateVaultHunter,
+    /// Ultimate Vault Hunter Upgrade Pack 2: Digistruct Peak Challenge.
+    DigistructPeak,
+    /// Commander Lilith & the Fight for Sanctuary.
+    CommanderLilith,
+}
+
+impl UpgradePack {
+    ///
+    /// Returns the highest level the DLC allows.
+    ///
+    pub fn max_level(self) -> i32 {
+        match self {
+            UpgradePack::None => 50,
+            UpgradePack::UltimateVaultHunter => 61,
+            UpgradePack::DigistructPeak => 72,
+            UpgradePack::CommanderLilith => MAX_LEVEL,
+        }
+    }
+}
+
+///
+/// Returns the experience points needed to reach a level, following the game's curve
+/// `ceil(60 * level ^ 2.8 - 60)`.
+///
+pub fn experience_for_level(level: i32) -> i32 {
+    let level = level.max(MIN_LEVEL) as f64;
+    (60.0 * level.powf(2.8) - 60.0).ceil() as i32
+}
+
+///
+/// Returns the level reached with the given experience points, capped at `cap`.
+///
+pub fn level_for_experience(points: i32, cap: i32) -> i32 {
+    let mut level = MIN_LEVEL;
+    while level < cap && experience_for_level(level + 1) <= points {
+        level += 1;
+    }
+    level
+}
+
+///
+/// Returns the number of skill points a character has earned by reaching a level.
+///
+pub fn skill_points_for_level(level: i32) -> i32 {
+    (level - FIRST_SKILL_POINT_LEVEL + 1).max(0)
+}
+
+fn apply_level(
+    save: &mut WillowTwoPlayerSaveGame,
+    level: i32,
+    points: i32,
+) -> Result<(), ExperienceError> {
+    let gained = skill_points_for_level(level) - skill_points_for_level(save.ExpLevel());
+    let skill_points = save.GeneralSkillPoints() + gained;
+    if skill_points < 0 {
+        return Err(ExperienceError::NotEnoughSkillPoints {
+            msg: format!(
+                "Level {} takes away {} spent skill points, respec first!",
+                level, -skill_points
+            ),
+        });
+    }
+    save.set_ExpLevel(level);
+    save.set_ExpPoints(points);
+    save.set_GeneralSkillPoints(skill_points);
+    Ok(())
+}
+
+///
+/// Sets the level and the experience points needed to reach it.
+///
+/// `GeneralSkillPoints` gains or loses the skill points of the levels in between. Lowering the
+/// level below the skill points already spent is refused.
+///
+pub fn set_level(
+    save: &mut WillowTwoPlayerSaveGame,
+    level: i32,
+    pack: UpgradePack,
+) -> Result<(), ExperienceError> {
+    let cap = pack.max_level();
+    if !(MIN_LEVEL..=cap).contains(&level) {
+        return Err(ExperienceError::InvalidLevel {
+            msg: format!("Level {} is outside of {} to {}!", level, MIN_LEVEL, cap),
+        });
+    }
+    apply_level(save, level, experience_for_level(level))
+}
+
+///
+/// Adds experience points and levels up, returns the number of levels gained.
+///
+/// Experience beyond the level cap is dropped, just like in the game. The stored experience is
+/// never lowered, even when it is already beyond the cap of `pack`.
+///
+pub fn add_experience(
+    save: &mut WillowTwoPlayerSaveGame,
+    points: i32,
+    pack: UpgradePack,
+) -> Result<i32, ExperienceError> {
+    if points < 0 {
+        return Err(ExperienceError::InvalidExperience {
+            msg: format!("Can not add {} experience points!", points),
+        });
+    }
+    let cap = pack.max_level();
+    // Only the added points are capped, a save already past the cap keeps its experience.
+    let total = save
+        .ExpPoints()
+        .saturating_add(points)
+        .min(experience_for_level(cap))
+        .max(save.ExpPoints());
+    let level = level_for_experience(total, cap).max(save.ExpLevel());
+    let gained = level - save.ExpLevel();
+    apply_level(save, level, total)?;
+    Ok(gained)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        add_experience, experience_for_level, set_level, skill_points_for_level, UpgradePack,
+        MAX_LEVEL,
+    };
+    use crate::load_test_save;
+
+    #[test]
+    fn experience_table_test() {
+        assert_eq!(experience_for_level(1), 0);
+        assert_eq!(experience_for_level(2), 358);
+        assert_eq!(skill_points_for_level(4), 0);
+        assert_eq!(skill_points_for_level(50), 46);
+
+        let save_game = load_test_save();
+        assert!(experience_for_level(save_game.ExpLevel()) <= save_game.ExpPoints());
+        assert!(experience_for_level(save_game.ExpLevel() + 1) > save_game.ExpPoints());
+    }
+
+    #[test]
+    fn set_level_test() {
+        let mut save_game = load_test_save();
+        set_level(&mut save_game, 50, UpgradePack::None).unwrap();
+        assert_eq!(save_game.ExpPoints(), experience_for_level(50));
+        assert_eq!(save_game.GeneralSkillPoints(), 13);
+        assert!(set_level(&mut save_game, 51, UpgradePack::None).is_err());
+        assert!(set_level(&mut save_game, 30, UpgradePack::None).is_err());
+
+        let points = experience_for_level(53) - save_game.ExpPoints();
+        assert_eq!(
+            add_experience(&mut save_game, points, UpgradePack::UltimateVaultHunter).unwrap(),
+            3
+        );
+        assert_eq!(save_game.ExpLevel(), 53);
+        assert_eq!(save_game.GeneralSkillPoints(), 16);
+        assert_eq!(
+            add_experience(&mut save_game, i32::MAX, UpgradePack::UltimateVaultHunter).unwrap(),
+            8
+        );
+        assert_eq!(save_game.ExpPoints(), experience_for_level(61));
+        assert!(set_level(&mut save_game, MAX_LEVEL, UpgradePack::CommanderLilith).is_ok());
+
+        let points = save_game.ExpPoints();
+        assert_eq!(
+            add_experience(&mut save_game, 1000, UpgradePack::None).unwrap(),
+            0
+        );
+        assert_eq!(save_game.ExpPoints(), points);
+        assert_eq!(save_game.ExpLevel(), MAX_LEVEL);
+    }
+}
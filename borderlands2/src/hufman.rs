@@ -99,3 +99,120 @@ pub fn decode(input: &[u8], output_size: usize) -> Vec<u8> {
     }
     output
 }
+
+///
+/// Returns the code of every symbol as (length, bits) by walking the tree.
+///
+/// The first bit of a code is stored in the highest used bit.
+///
+fn build_codes(tree: &[Node], index: usize, length: usize, bits: u64, codes: &mut [(usize, u64)]) {
+    let node = tree[index];
+    if node.is_leaf {
+        codes[node.symbol as usize] = (length, bits);
+    } else {
+        build_codes(tree, node.left as usize, length + 1, bits << 1, codes);
+        build_codes(
+            tree,
+            node.right as usize,
+            length + 1,
+            (bits << 1) | 1,
+            codes,
+        );
+    }
+}
+
+///
+/// Appends the tree in the format read by decode_node.
+///
+fn encode_node(tree: &[Node], index: usize, output: &mut bit_vec::BitVec) {
+    let node = tree[index];
+    output.push(node.is_leaf);
+    if node.is_leaf {
+        for i in (0..8).rev() {
+            output.push((node.symbol >> i) & 1 == 1);
+        }
+    } else {
+        encode_node(tree, node.left as usize, output);
+        encode_node(tree, node.right as usize, output);
+    }
+}
+
+///
+/// Encodes the input into a hufmann tree followed by the encoded data.
+///
+/// The tree is built the way the game builds it: of two nodes with the same count the one
+/// created last is merged first and becomes the left child, so re-encoding the data of a save
+/// reproduces the bytes written by the game. The output can be decoded again with decode and
+/// the input length.
+///
+pub fn encode(input: &[u8]) -> Vec<u8> {
+    let mut counts = [0usize; 256];
+    for byte in input {
+        counts[*byte as usize] += 1;
+    }
+
+    let mut tree: Vec<Node> = Vec::with_capacity(511);
+    let mut queue = std::collections::BinaryHeap::new();
+    for (symbol, count) in counts.iter().enumerate() {
+        if *count > 0 {
+            queue.push(std::cmp::Reverse((*count, std::cmp::Reverse(tree.len()))));
+            tree.push(Node {
+                symbol: symbol as u8,
+                is_leaf: true,
+                left: -1,
+                right: -1,
+            });
+        }
+    }
+    if tree.is_empty() {
+        tree.push(Node {
+            symbol: 0,
+            is_leaf: true,
+            left: -1,
+            right: -1,
+        });
+        queue.push(std::cmp::Reverse((0, std::cmp::Reverse(0))));
+    }
+    while queue.len() > 1 {
+        let std::cmp::Reverse((left_count, std::cmp::Reverse(left))) = queue.pop().unwrap();
+        let std::cmp::Reverse((right_count, std::cmp::Reverse(right))) = queue.pop().unwrap();
+        queue.push(std::cmp::Reverse((
+            left_count + right_count,
+            std::cmp::Reverse(tree.len()),
+        )));
+        tree.push(Node {
+            symbol: 0,
+            is_leaf: false,
+            left: left as i64,
+            right: right as i64,
+        });
+    }
+    let root = (queue.pop().unwrap().0 .1).0;
+
+    let mut codes = [(0usize, 0u64); 256];
+    build_codes(&tree, root, 0, 0, &mut codes);
+
+    let mut output = bit_vec::BitVec::new();
+    encode_node(&tree, root, &mut output);
+    for byte in input {
+        let (length, bits) = codes[*byte as usize];
+        for i in (0..length).rev() {
+            output.push((bits >> i) & 1 == 1);
+        }
+    }
+    output.to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode};
+
+    #[test]
+    fn encode_decode_test() {
+        let input = b"Borderlands 2 save games are hufmann encoded.".to_vec();
+        assert_eq!(decode(&encode(&input), input.len()), input);
+
+        let single = vec![7u8; 32];
+        assert_eq!(decode(&encode(&single), single.len()), single);
+    }
+}
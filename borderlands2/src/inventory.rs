@@ -0,0 +1,335 @@
+use crate::bank::{self, BankError};
+use crate::convert::{self, ConvertError};
+use crate::equipment::GearIndex;
+use crate::ItemData::ItemData;
+use crate::PackedItemData::PackedItemData;
+use crate::PackedWeaponData::PackedWeaponData;
+use crate::QuickWeaponSlot::QuickWeaponSlot;
+use crate::WeaponData::WeaponData;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+/// Backpack slots without any SDU.
+pub const BACKPACK_BASE_SLOTS: i32 = 12;
+/// Backpack slots added by every SDU level.
+pub const BACKPACK_SLOTS_PER_LEVEL: i32 = 3;
+/// Highest backpack SDU level sold by the black market.
+pub const MAX_BACKPACK_LEVEL: i32 = 9;
+/// Position of the backpack SDU in `BlackMarketUpgrades`.
+pub const BACKPACK_UPGRADE_INDEX: usize = 7;
+
+#[derive(Debug)]
+pub enum InventoryError {
+    BackpackFull { msg: String },
+    BankFull { msg: String },
+    InvalidLevel { msg: String },
+    ConvertError { msg: String },
+    NoSuchGear { msg: String },
+}
+
+impl std::fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InventoryError::BackpackFull { msg } => write!(f, "BackpackFull: {}", msg),
+            InventoryError::BankFull { msg } => write!(f, "BankFull: {}", msg),
+            InventoryError::InvalidLevel { msg } => write!(f, "InvalidLevel: {}", msg),
+            InventoryError::ConvertError { msg } => write!(f, "ConvertError: {}", msg),
+            InventoryError::NoSuchGear { msg } => write!(f, "NoSuchGear: {}", msg),
+        }
+    }
+}
+
+impl From<ConvertError> for InventoryError {
+    fn from(err: ConvertError) -> Self {
+        InventoryError::ConvertError {
+            msg: err.to_string(),
+        }
+    }
+}
+
+impl From<BankError> for InventoryError {
+    fn from(err: BankError) -> Self {
+        match err {
+            BankError::BankFull { msg } => InventoryError::BankFull { msg },
+            BankError::BackpackFull { msg } => InventoryError::BackpackFull { msg },
+            BankError::NoSuchGear { msg } => InventoryError::NoSuchGear { msg },
+            BankError::InvalidLevel { msg } => InventoryError::InvalidLevel { msg },
+            BankError::ConvertError { msg } => InventoryError::ConvertError { msg },
+        }
+    }
+}
+
+///
+/// What to do with gear that does not fit into the backpack.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// Return `InventoryError::BackpackFull`.
+    Refuse,
+    /// Store the gear in the bank instead.
+    ///
+    /// Unpacked gear is packed first, the serial is encrypted with `seed`.
+    Bank { seed: i32 },
+}
+
+///
+/// Where added gear ended up.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Placement {
+    Backpack(GearIndex),
+    Bank(usize),
+}
+
+///
+/// Returns the number of backpack slots, `InventorySlotData.InventorySlotMax`.
+///
+pub fn backpack_capacity(save: &WillowTwoPlayerSaveGame) -> usize {
+    save.InventorySlotData.InventorySlotMax().max(0) as usize
+}
+
+///
+/// Returns the number of backpack slots in use.
+///
+/// Weapons in a quick slot and equipped items do not take a slot. Packed items with a quantity
+/// below one are placeholders kept by the game and do not take a slot either.
+///
+pub fn backpack_used(save: &WillowTwoPlayerSaveGame) -> usize {
+    let weapons = save
+        .WeaponData
+        .iter()
+        .filter(|weapon| weapon.QuickSlot() == QuickWeaponSlot::None)
+        .count();
+    let packed_weapons = save
+        .PackedWeaponData
+        .iter()
+        .filter(|weapon| weapon.QuickSlot() == QuickWeaponSlot::None)
+        .count();
+    let items = save.ItemData.iter().filter(|item| !item.Equipped()).count();
+    let packed_items = save
+        .PackedItemData
+        .iter()
+        .filter(|item| !item.Equipped() && item.Quantity() > 0)
+        .count();
+    weapons + packed_weapons + items + packed_items
+}
+
+///
+/// Returns the number of free backpack slots.
+///
+pub fn backpack_free(save: &WillowTwoPlayerSaveGame) -> usize {
+    backpack_capacity(save).saturating_sub(backpack_used(save))
+}
+
+///
+/// Returns the backpack SDU level stored in `BlackMarketUpgrades`.
+///
+pub fn backpack_level(save: &WillowTwoPlayerSaveGame) -> i32 {
+    save.BlackMarketUpgrades
+        .get(BACKPACK_UPGRADE_INDEX)
+        .copied()
+        .unwrap_or(0)
+}
+
+///
+/// Sets the backpack SDU level and the matching `InventorySlotMax`.
+///
+/// The backpack has no entry in `ResourceData`, only ammo pools are stored there.
+/// Lowering the level below what the current backpack content needs is refused.
+///
+pub fn set_backpack_level(
+    save: &mut WillowTwoPlayerSaveGame,
+    level: i32,
+) -> Result<(), InventoryError> {
+    if !(0..=MAX_BACKPACK_LEVEL).contains(&level) {
+        return Err(InventoryError::InvalidLevel {
+            msg: format!(
+                "Backpack level {} is not between 0 and {}!",
+                level, MAX_BACKPACK_LEVEL
+            ),
+        });
+    }
+    let capacity = BACKPACK_BASE_SLOTS + level * BACKPACK_SLOTS_PER_LEVEL;
+    let used = backpack_used(save);
+    if used > capacity as usize {
+        return Err(InventoryError::InvalidLevel {
+            msg: format!(
+                "Backpack level {} has {} slots but {} are in use!",
+                level, capacity, used
+            ),
+        });
+    }
+
+    if save.BlackMarketUpgrades.len() <= BACKPACK_UPGRADE_INDEX {
+        save.BlackMarketUpgrades
+            .resize(BACKPACK_UPGRADE_INDEX + 1, 0);
+    }
+    save.BlackMarketUpgrades[BACKPACK_UPGRADE_INDEX] = level;
+    save.InventorySlotData
+        .mut_or_insert_default()
+        .set_InventorySlotMax(capacity);
+    Ok(())
+}
+
+///
+/// Returns true if the backpack can take `count` more gear.
+///
+fn backpack_fits(save: &WillowTwoPlayerSaveGame, count: usize) -> bool {
+    backpack_used(save) + count <= backpack_capacity(save)
+}
+
+fn backpack_full(save: &WillowTwoPlayerSaveGame) -> InventoryError {
+    InventoryError::BackpackFull {
+        msg: format!("All {} backpack slots are in use!", backpack_capacity(save)),
+    }
+}
+
+fn push_bank(
+    save: &mut WillowTwoPlayerSaveGame,
+    serial: Vec<u8>,
+    quantity: i32,
+) -> Result<Placement, InventoryError> {
+    Ok(Placement::Bank(bank::deposit_serial(
+        save,
+        serial,
+        quantity.max(1) as usize,
+    )?))
+}
+
+///
+/// Adds an unpacked weapon to the backpack.
+///
+/// A weapon in a quick slot does not need a free backpack slot.
+///
+pub fn add_weapon(
+    save: &mut WillowTwoPlayerSaveGame,
+    weapon: WeaponData,
+    overflow: Overflow,
+) -> Result<Placement, InventoryError> {
+    if weapon.QuickSlot() == QuickWeaponSlot::None && !backpack_fits(save, 1) {
+        return match overflow {
+            Overflow::Refuse => Err(backpack_full(save)),
+            Overflow::Bank { seed } => push_bank(save, convert::weapon_serial(&weapon, seed)?, 1),
+        };
+    }
+    save.WeaponData.push(weapon);
+    Ok(Placement::Backpack(GearIndex::Weapon(
+        save.WeaponData.len() - 1,
+    )))
+}
+
+///
+/// Adds a packed weapon to the backpack.
+///
+/// A weapon in a quick slot does not need a free backpack slot.
+///
+pub fn add_packed_weapon(
+    save: &mut WillowTwoPlayerSaveGame,
+    weapon: PackedWeaponData,
+    overflow: Overflow,
+) -> Result<Placement, InventoryError> {
+    if weapon.QuickSlot() == QuickWeaponSlot::None && !backpack_fits(save, 1) {
+        return match overflow {
+            Overflow::Refuse => Err(backpack_full(save)),
+            Overflow::Bank { .. } => push_bank(save, weapon.InventorySerialNumber().to_vec(), 1),
+        };
+    }
+    save.PackedWeaponData.push(weapon);
+    Ok(Placement::Backpack(GearIndex::PackedWeapon(
+        save.PackedWeaponData.len() - 1,
+    )))
+}
+
+///
+/// Adds an unpacked item to the backpack.
+///
+/// An equipped item does not need a free backpack slot.
+///
+pub fn add_item(
+    save: &mut WillowTwoPlayerSaveGame,
+    item: ItemData,
+    overflow: Overflow,
+) -> Result<Placement, InventoryError> {
+    if !item.Equipped() && !backpack_fits(save, 1) {
+        return match overflow {
+            Overflow::Refuse => Err(backpack_full(save)),
+            Overflow::Bank { seed } => {
+                push_bank(save, convert::item_serial(&item, seed)?, item.Quantity())
+            }
+        };
+    }
+    save.ItemData.push(item);
+    Ok(Placement::Backpack(GearIndex::Item(
+        save.ItemData.len() - 1,
+    )))
+}
+
+///
+/// Adds a packed item to the backpack.
+///
+/// An equipped item does not need a free backpack slot.
+///
+pub fn add_packed_item(
+    save: &mut WillowTwoPlayerSaveGame,
+    item: PackedItemData,
+    overflow: Overflow,
+) -> Result<Placement, InventoryError> {
+    if !item.Equipped() && item.Quantity() > 0 && !backpack_fits(save, 1) {
+        return match overflow {
+            Overflow::Refuse => Err(backpack_full(save)),
+            Overflow::Bank { .. } => {
+                push_bank(save, item.InventorySerialNumber().to_vec(), item.Quantity())
+            }
+        };
+    }
+    save.PackedItemData.push(item);
+    Ok(Placement::Backpack(GearIndex::PackedItem(
+        save.PackedItemData.len() - 1,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        add_packed_weapon, backpack_capacity, backpack_free, backpack_level, backpack_used,
+        set_backpack_level, InventoryError, Overflow, Placement,
+    };
+    use crate::load_test_save;
+    use crate::QuickWeaponSlot::QuickWeaponSlot;
+
+    #[test]
+    fn backpack_level_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(backpack_level(&save_game), 9);
+        assert_eq!(backpack_capacity(&save_game), 39);
+        assert_eq!(backpack_used(&save_game), 27);
+
+        set_backpack_level(&mut save_game, 6).unwrap();
+        assert_eq!(save_game.BlackMarketUpgrades[7], 6);
+        assert_eq!(save_game.InventorySlotData.InventorySlotMax(), 30);
+        assert!(set_backpack_level(&mut save_game, 4).is_err());
+        assert!(set_backpack_level(&mut save_game, 10).is_err());
+    }
+
+    #[test]
+    fn backpack_overflow_test() {
+        let mut save_game = load_test_save();
+        let mut weapon = save_game.PackedWeaponData[0].clone();
+        weapon.set_QuickSlot(QuickWeaponSlot::None);
+
+        for _ in 0..backpack_free(&save_game) {
+            add_packed_weapon(&mut save_game, weapon.clone(), Overflow::Refuse).unwrap();
+        }
+        assert!(matches!(
+            add_packed_weapon(&mut save_game, weapon.clone(), Overflow::Refuse),
+            Err(InventoryError::BackpackFull { .. })
+        ));
+        assert_eq!(
+            add_packed_weapon(&mut save_game, weapon.clone(), Overflow::Bank { seed: 0 }).unwrap(),
+            Placement::Bank(10)
+        );
+        assert_eq!(
+            save_game.BankSlots[10].InventorySerialNumber(),
+            weapon.InventorySerialNumber()
+        );
+    }
+}
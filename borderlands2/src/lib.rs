@@ -1,5 +1,29 @@
 include!(concat!(env!("OUT_DIR"), "/protos/mod.rs"));
+pub mod ammo;
+pub mod assets;
+pub mod bank;
+pub mod blackmarket;
+pub mod builder;
+pub mod challenges;
+pub mod convert;
+pub mod currency;
+pub mod duplicates;
+pub mod equipment;
+pub mod experience;
 mod hufman;
+pub mod inventory;
+pub mod loadout;
+pub mod lockout;
+pub mod missions;
+pub mod planner;
+pub mod playthrough;
+pub mod regions;
+pub mod rewards;
+pub mod serial;
+pub mod skills;
+pub mod story;
+pub mod transfer;
+pub mod travel;
 
 extern crate minilzo_rs;
 extern crate protobuf;
@@ -11,7 +35,7 @@ use std::io::Read;
 
 use std::convert::TryFrom;
 
-use hufman::decode;
+use hufman::{decode, encode};
 
 use protobuf::Message;
 use sha1::{Digest, Sha1};
@@ -33,6 +57,28 @@ impl std::fmt::Display for LoadSaveError {
     }
 }
 
+#[derive(Debug)]
+pub enum WriteSaveError {
+    IOError { msg: String },
+    SerializationError { msg: String },
+    CompressionError { msg: String },
+}
+
+impl std::fmt::Display for WriteSaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            WriteSaveError::IOError { msg } => write!(f, "IOError: {}", msg),
+            WriteSaveError::SerializationError { msg } => write!(f, "SerializationError: {}", msg),
+            WriteSaveError::CompressionError { msg } => write!(f, "CompressionError: {}", msg),
+        }
+    }
+}
+
+/// Magic number at the start of the inner save data.
+const SAVE_MAGIC: &[u8; 3] = b"WSG";
+/// Version of the inner save data written by the game.
+const SAVE_VERSION: u32 = 2;
+
 ///
 ///
 pub fn load_save(
@@ -174,6 +220,82 @@ pub unsafe fn handle_uncompressed_data(
     Ok(save_game)
 }
 
+///
+/// Writes a save game to a file in the format read by `load_save`.
+///
+pub fn write_save(
+    save_file_path: &str,
+    save_game: &WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame,
+) -> Result<(), WriteSaveError> {
+    let buffer = write_save_mem(save_game)?;
+    match fs::write(save_file_path, buffer) {
+        Ok(_) => Ok(()),
+        Err(msg) => Err(WriteSaveError::IOError {
+            msg: msg.to_string(),
+        }),
+    }
+}
+
+///
+/// Serializes a save game into the format read by `load_save_mem`.
+///
+/// The protobuf data is hufmann encoded and wrapped into the inner header, which is then
+/// compressed with LZO and prefixed with its size and SHA1 hash.
+///
+pub fn write_save_mem(
+    save_game: &WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame,
+) -> Result<Vec<u8>, WriteSaveError> {
+    let inner_data = match save_game.write_to_bytes() {
+        Ok(inner_data) => inner_data,
+        Err(msg) => {
+            return Err(WriteSaveError::SerializationError {
+                msg: msg.to_string(),
+            })
+        }
+    };
+    let mut inner_compressed_data = encode(&inner_data);
+    // The game pads the encoded data with four zero bytes.
+    inner_compressed_data.extend_from_slice(&[0; 4]);
+
+    let inner_size = (SAVE_MAGIC.len() + 12 + inner_compressed_data.len()) as u32;
+    let mut uncompressed_data = Vec::with_capacity(inner_size as usize + 4);
+    uncompressed_data.extend_from_slice(&inner_size.to_be_bytes());
+    uncompressed_data.extend_from_slice(SAVE_MAGIC);
+    uncompressed_data.extend_from_slice(&SAVE_VERSION.to_le_bytes());
+    uncompressed_data.extend_from_slice(&serial::crc32(&inner_data).to_le_bytes());
+    uncompressed_data.extend_from_slice(&(inner_data.len() as i32).to_le_bytes());
+    uncompressed_data.extend_from_slice(&inner_compressed_data);
+
+    let compressed_data =
+        match minilzo_rs::LZO::init().and_then(|mut lzo| lzo.compress(&uncompressed_data)) {
+            Ok(compressed_data) => compressed_data,
+            Err(err) => {
+                return Err(WriteSaveError::CompressionError {
+                    msg: format!("Could not compress using LZO: {}", err),
+                })
+            }
+        };
+
+    let mut buffer_data = (uncompressed_data.len() as u32).to_be_bytes().to_vec();
+    buffer_data.extend_from_slice(&compressed_data);
+
+    let mut hasher = Sha1::new();
+    hasher.update(&buffer_data);
+    let mut buffer = hasher.finalize().to_vec();
+    buffer.extend_from_slice(&buffer_data);
+    Ok(buffer)
+}
+
+///
+/// Loads the save game shared by the tests.
+///
+#[cfg(test)]
+pub(crate) fn load_test_save() -> WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame {
+    let cwd = std::env::current_dir().unwrap();
+    let save_game_file_path = cwd.join("resources").join("Save0001.sav");
+    load_save(save_game_file_path.to_str().unwrap()).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use std::env;
@@ -198,4 +320,32 @@ mod tests {
         let load_save_result = super::load_save(save_game_file_path_string);
         assert!(load_save_result.is_err());
     }
+
+    #[test]
+    fn write_save_test() {
+        let cwd = env::current_dir().unwrap();
+        let save_game_file_path = cwd.join("resources").join("Save0001.sav");
+        let save_game = super::load_save(save_game_file_path.to_str().unwrap()).unwrap();
+
+        let buffer = super::write_save_mem(&save_game).unwrap();
+        let reloaded_save_game = super::load_save_mem(buffer).unwrap();
+        assert_eq!(save_game, reloaded_save_game);
+    }
+
+    fn decompress(buffer: &[u8]) -> Vec<u8> {
+        let size = u32::from_be_bytes(buffer[20..24].try_into().unwrap()) as usize;
+        let lzo = minilzo_rs::LZO::init().unwrap();
+        lzo.decompress_safe(&buffer[24..], size).unwrap()
+    }
+
+    #[test]
+    fn write_save_bytes_test() {
+        let cwd = env::current_dir().unwrap();
+        let save_game_file_path = cwd.join("resources").join("Save0001.sav");
+        let original = std::fs::read(&save_game_file_path).unwrap();
+        let save_game = super::load_save(save_game_file_path.to_str().unwrap()).unwrap();
+
+        let buffer = super::write_save_mem(&save_game).unwrap();
+        assert_eq!(decompress(&buffer), decompress(&original));
+    }
 }
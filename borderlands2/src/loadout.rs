@@ -0,0 +1,463 @@
+use std::collections::HashSet;
+use std::fs;
+
+use crate::bank;
+use crate::convert;
+use crate::equipment::{self, GearIndex};
+use crate::inventory;
+use crate::serial::{next_seed, reseed_serial};
+use crate::PlayerMark::PlayerMark;
+use crate::QuickWeaponSlot::QuickWeaponSlot;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+#[derive(Debug)]
+pub enum LoadoutError {
+    IOError { msg: String },
+    ParsingError { msg: String },
+    NotFound { msg: String },
+    InvalidSlot { msg: String },
+    NoSpace { msg: String },
+    ConvertError { msg: String },
+}
+
+impl std::fmt::Display for LoadoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LoadoutError::IOError { msg } => write!(f, "IOError: {}", msg),
+            LoadoutError::ParsingError { msg } => write!(f, "ParsingError: {}", msg),
+            LoadoutError::NotFound { msg } => write!(f, "NotFound: {}", msg),
+            LoadoutError::InvalidSlot { msg } => write!(f, "InvalidSlot: {}", msg),
+            LoadoutError::NoSpace { msg } => write!(f, "NoSpace: {}", msg),
+            LoadoutError::ConvertError { msg } => write!(f, "ConvertError: {}", msg),
+        }
+    }
+}
+
+///
+/// A named set of equipped gear.
+///
+/// Gear is identified by its serial encrypted with a zero seed, so a loadout still matches
+/// after the gear was moved, re-encrypted or packed.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Loadout {
+    pub name: String,
+    /// The weapons in the quick slots.
+    pub weapons: Vec<(QuickWeaponSlot, Vec<u8>)>,
+    /// The equipped shield, grenade mod, class mod and relic.
+    pub items: Vec<Vec<u8>>,
+}
+
+///
+/// Returns the serial of a weapon or item encrypted with a zero seed.
+///
+fn normalized_serial(save: &WillowTwoPlayerSaveGame, gear: GearIndex) -> Option<Vec<u8>> {
+    match gear {
+        GearIndex::Weapon(index) => convert::weapon_serial(save.WeaponData.get(index)?, 0).ok(),
+        GearIndex::PackedWeapon(index) => {
+            reseed_serial(save.PackedWeaponData.get(index)?.InventorySerialNumber(), 0).ok()
+        }
+        GearIndex::Item(index) => convert::item_serial(save.ItemData.get(index)?, 0).ok(),
+        GearIndex::PackedItem(index) => {
+            reseed_serial(save.PackedItemData.get(index)?.InventorySerialNumber(), 0).ok()
+        }
+    }
+}
+
+fn unreadable(gear: GearIndex) -> LoadoutError {
+    LoadoutError::ConvertError {
+        msg: format!("Could not determine the serial of {:?}!", gear),
+    }
+}
+
+///
+/// Takes a snapshot of the weapons in the quick slots and the equipped items.
+///
+pub fn snapshot(save: &WillowTwoPlayerSaveGame, name: &str) -> Result<Loadout, LoadoutError> {
+    let mut loadout = Loadout {
+        name: name.to_string(),
+        weapons: Vec::new(),
+        items: Vec::new(),
+    };
+    for weapon in equipment::weapons(save) {
+        let slot = equipment::quick_slot(save, weapon).unwrap_or(QuickWeaponSlot::None);
+        if slot != QuickWeaponSlot::None {
+            let serial = normalized_serial(save, weapon).ok_or_else(|| unreadable(weapon))?;
+            loadout.weapons.push((slot, serial));
+        }
+    }
+    for item in equipment::items(save) {
+        if equipment::is_equipped(save, item) {
+            let serial = normalized_serial(save, item).ok_or_else(|| unreadable(item))?;
+            loadout.items.push(serial);
+        }
+    }
+    Ok(loadout)
+}
+
+///
+/// Finds unclaimed gear with the given serial in the backpack.
+///
+fn find_in_backpack(
+    save: &WillowTwoPlayerSaveGame,
+    gear: Vec<GearIndex>,
+    serial: &[u8],
+    claimed: &HashSet<GearIndex>,
+) -> Option<GearIndex> {
+    gear.into_iter().find(|gear| {
+        !claimed.contains(gear) && normalized_serial(save, *gear).as_deref() == Some(serial)
+    })
+}
+
+///
+/// Moves gear with the given serial from the bank into the packed gear of the backpack.
+///
+/// The gear does not need a free backpack slot, as it is equipped right after.
+///
+fn take_from_bank(
+    save: &mut WillowTwoPlayerSaveGame,
+    serial: &[u8],
+    is_weapon: bool,
+) -> Option<GearIndex> {
+    let index = save.BankSlots.iter().position(|slot| {
+        reseed_serial(slot.InventorySerialNumber(), 0)
+            .ok()
+            .as_deref()
+            == Some(serial)
+    })?;
+    let slot = save.BankSlots.remove(index);
+    if is_weapon {
+        save.PackedWeaponData.push(bank::bank_weapon(
+            slot.InventorySerialNumber(),
+            PlayerMark::Standard,
+        ));
+        Some(GearIndex::PackedWeapon(save.PackedWeaponData.len() - 1))
+    } else {
+        save.PackedItemData.push(bank::bank_item(
+            slot.InventorySerialNumber(),
+            1,
+            PlayerMark::Standard,
+        ));
+        Some(GearIndex::PackedItem(save.PackedItemData.len() - 1))
+    }
+}
+
+fn not_found(loadout: &Loadout, serial: &[u8]) -> LoadoutError {
+    LoadoutError::NotFound {
+        msg: format!(
+            "Gear {} of loadout {} is neither in the backpack nor in the bank!",
+            to_hex(serial),
+            loadout.name
+        ),
+    }
+}
+
+fn gear_index(gear: GearIndex) -> usize {
+    match gear {
+        GearIndex::Weapon(index)
+        | GearIndex::PackedWeapon(index)
+        | GearIndex::Item(index)
+        | GearIndex::PackedItem(index) => index,
+    }
+}
+
+///
+/// Equips the gear of a loadout.
+///
+/// All other gear is unequipped. Gear of the loadout stored in the bank is moved into the
+/// backpack. If the unequipped gear no longer fits into the backpack, it is moved into the
+/// bank, unpacked gear is packed for that with `seed` and the seeds following it, one per
+/// weapon or item. The save is only changed if the whole loadout could be applied.
+///
+pub fn apply(
+    save: &mut WillowTwoPlayerSaveGame,
+    loadout: &Loadout,
+    mut seed: i32,
+) -> Result<(), LoadoutError> {
+    let mut working = save.clone();
+
+    let mut previously_equipped = Vec::new();
+    for weapon in equipment::weapons(&working) {
+        if equipment::quick_slot(&working, weapon) != Some(QuickWeaponSlot::None) {
+            previously_equipped.push(weapon);
+        }
+    }
+    for item in equipment::items(&working) {
+        if equipment::is_equipped(&working, item) {
+            previously_equipped.push(item);
+        }
+    }
+    for gear in &previously_equipped {
+        let result = match gear {
+            GearIndex::Weapon(_) | GearIndex::PackedWeapon(_) => {
+                equipment::unequip_weapon(&mut working, *gear)
+            }
+            GearIndex::Item(_) | GearIndex::PackedItem(_) => {
+                equipment::unequip_item(&mut working, *gear)
+            }
+        };
+        result.map_err(|err| LoadoutError::NotFound {
+            msg: err.to_string(),
+        })?;
+    }
+
+    let mut claimed = HashSet::new();
+    for (slot, serial) in &loadout.weapons {
+        if !equipment::is_slot_unlocked(&working, *slot) {
+            return Err(LoadoutError::InvalidSlot {
+                msg: format!(
+                    "Quick slot {:?} of loadout {} is not available!",
+                    slot, loadout.name
+                ),
+            });
+        }
+        let weapon =
+            match find_in_backpack(&working, equipment::weapons(&working), serial, &claimed) {
+                Some(weapon) => weapon,
+                None => take_from_bank(&mut working, serial, true)
+                    .ok_or_else(|| not_found(loadout, serial))?,
+            };
+        claimed.insert(weapon);
+        if let Err(err) = equipment::equip_weapon(&mut working, weapon, *slot) {
+            return Err(LoadoutError::InvalidSlot {
+                msg: err.to_string(),
+            });
+        }
+    }
+    for serial in &loadout.items {
+        let item = match find_in_backpack(&working, equipment::items(&working), serial, &claimed) {
+            Some(item) => item,
+            None => take_from_bank(&mut working, serial, false)
+                .ok_or_else(|| not_found(loadout, serial))?,
+        };
+        claimed.insert(item);
+        match item {
+            GearIndex::Item(index) => working.ItemData[index].set_Equipped(true),
+            GearIndex::PackedItem(index) => working.PackedItemData[index].set_Equipped(true),
+            _ => {
+                return Err(LoadoutError::NotFound {
+                    msg: format!("{:?} of loadout {} is not an item!", item, loadout.name),
+                })
+            }
+        }
+    }
+
+    let mut spare: Vec<GearIndex> = previously_equipped
+        .into_iter()
+        .filter(|gear| !claimed.contains(gear))
+        .collect();
+    spare.sort_by_key(|gear| std::cmp::Reverse(gear_index(*gear)));
+    let mut spare = spare.into_iter();
+    while inventory::backpack_used(&working) > inventory::backpack_capacity(&working) {
+        let gear = match spare.next() {
+            Some(gear) => gear,
+            None => break,
+        };
+        if let Err(err) = bank::deposit(&mut working, gear, seed) {
+            return Err(LoadoutError::NoSpace {
+                msg: err.to_string(),
+            });
+        }
+        seed = next_seed(seed);
+    }
+    if inventory::backpack_used(&working) > inventory::backpack_capacity(&working) {
+        return Err(LoadoutError::NoSpace {
+            msg: format!(
+                "The unequipped gear does not fit into the backpack for loadout {}!",
+                loadout.name
+            ),
+        });
+    }
+
+    *save = working;
+    Ok(())
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [_, _] => u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn slot_name(slot: QuickWeaponSlot) -> &'static str {
+    match slot {
+        QuickWeaponSlot::None => "None",
+        QuickWeaponSlot::Up => "Up",
+        QuickWeaponSlot::Down => "Down",
+        QuickWeaponSlot::Left => "Left",
+        QuickWeaponSlot::Right => "Right",
+    }
+}
+
+fn parse_slot(name: &str) -> Option<QuickWeaponSlot> {
+    match name {
+        "Up" => Some(QuickWeaponSlot::Up),
+        "Down" => Some(QuickWeaponSlot::Down),
+        "Left" => Some(QuickWeaponSlot::Left),
+        "Right" => Some(QuickWeaponSlot::Right),
+        _ => None,
+    }
+}
+
+///
+/// Formats loadouts in the sidecar file format.
+///
+/// Every loadout starts with its name in brackets, followed by one `weapon <slot> <serial>`
+/// or `item <serial>` line per piece of gear. Serials are written as hex.
+///
+pub fn format_loadouts(loadouts: &[Loadout]) -> String {
+    let mut text = String::new();
+    for loadout in loadouts {
+        text.push_str(&format!("[{}]\n", loadout.name));
+        for (slot, serial) in &loadout.weapons {
+            text.push_str(&format!("weapon {} {}\n", slot_name(*slot), to_hex(serial)));
+        }
+        for serial in &loadout.items {
+            text.push_str(&format!("item {}\n", to_hex(serial)));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+///
+/// Parses loadouts from the sidecar file format, see `format_loadouts`.
+///
+/// Empty lines and lines starting with `#` are ignored.
+///
+pub fn parse_loadouts(text: &str) -> Result<Vec<Loadout>, LoadoutError> {
+    let mut loadouts: Vec<Loadout> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || LoadoutError::ParsingError {
+            msg: format!("Invalid loadout line {}: {}", number + 1, line),
+        };
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            loadouts.push(Loadout {
+                name: name.to_string(),
+                weapons: Vec::new(),
+                items: Vec::new(),
+            });
+            continue;
+        }
+        let loadout = loadouts.last_mut().ok_or_else(invalid)?;
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields[..] {
+            ["weapon", slot, serial] => {
+                let slot = parse_slot(slot).ok_or_else(invalid)?;
+                loadout
+                    .weapons
+                    .push((slot, from_hex(serial).ok_or_else(invalid)?));
+            }
+            ["item", serial] => loadout.items.push(from_hex(serial).ok_or_else(invalid)?),
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(loadouts)
+}
+
+///
+/// Returns the path of the loadout sidecar file of a save.
+///
+pub fn sidecar_path(save_file_path: &str) -> String {
+    format!("{}.loadouts", save_file_path)
+}
+
+///
+/// Reads the loadouts from a sidecar file. A missing file contains no loadouts.
+///
+pub fn read_loadouts(path: &str) -> Result<Vec<Loadout>, LoadoutError> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse_loadouts(&text),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(LoadoutError::IOError {
+            msg: err.to_string(),
+        }),
+    }
+}
+
+///
+/// Writes loadouts to a sidecar file.
+///
+pub fn write_loadouts(path: &str, loadouts: &[Loadout]) -> Result<(), LoadoutError> {
+    fs::write(path, format_loadouts(loadouts)).map_err(|err| LoadoutError::IOError {
+        msg: err.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, format_loadouts, parse_loadouts, snapshot, Loadout};
+    use crate::bank;
+    use crate::equipment::{self, EquipmentIssue, GearIndex};
+    use crate::QuickWeaponSlot::QuickWeaponSlot;
+
+    use crate::load_test_save;
+    use protobuf::Enum;
+
+    fn sorted(mut loadout: Loadout) -> Loadout {
+        loadout
+            .weapons
+            .sort_by_key(|(slot, serial)| (slot.value(), serial.clone()));
+        loadout.items.sort();
+        loadout
+    }
+
+    #[test]
+    fn loadout_format_test() {
+        let save_game = load_test_save();
+        let loadout = snapshot(&save_game, "Mobbing").unwrap();
+        assert_eq!(loadout.weapons.len(), 4);
+        assert_eq!(loadout.items.len(), 4);
+
+        let text = format!(
+            "# Loadouts\n{}",
+            format_loadouts(std::slice::from_ref(&loadout))
+        );
+        assert_eq!(parse_loadouts(&text).unwrap(), vec![loadout]);
+        assert!(parse_loadouts("weapon Up 00").is_err());
+        assert!(parse_loadouts("[Broken]\nweapon Middle 00").is_err());
+    }
+
+    #[test]
+    fn loadout_apply_test() {
+        let mut save_game = load_test_save();
+        let loadout = snapshot(&save_game, "Raid").unwrap();
+
+        let up = equipment::weapon_in_slot(&save_game, QuickWeaponSlot::Up).unwrap();
+        bank::deposit(&mut save_game, up, 0).unwrap();
+        equipment::equip_weapon(
+            &mut save_game,
+            GearIndex::PackedWeapon(4),
+            QuickWeaponSlot::Down,
+        )
+        .unwrap();
+        assert_ne!(
+            sorted(snapshot(&save_game, "Raid").unwrap()),
+            sorted(loadout.clone())
+        );
+
+        apply(&mut save_game, &loadout, 0).unwrap();
+        assert_eq!(
+            sorted(snapshot(&save_game, "Raid").unwrap()),
+            sorted(loadout.clone())
+        );
+        assert_eq!(save_game.BankSlots.len(), 10);
+        // The asset tables can not tell the categories of the real items.
+        assert!(equipment::check_equipment(&save_game)
+            .iter()
+            .all(|issue| matches!(issue, EquipmentIssue::UnknownCategory { .. })));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::LockoutData::LockoutData;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+#[derive(Debug)]
+pub enum LockoutError {
+    NoSuchLockout { msg: String },
+}
+
+impl std::fmt::Display for LockoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LockoutError::NoSuchLockout { msg } => write!(f, "NoSuchLockout: {}", msg),
+        }
+    }
+}
+
+///
+/// Returns the seconds of play left until a lockout expires, 0 once it has expired.
+///
+/// Lockouts run on play time, `LockoutData.Time` is the `TotalPlayTime` at which the
+/// encounter can be repeated. Both count seconds, Save0001 has 136886 seconds of play.
+///
+pub fn remaining_time(save: &WillowTwoPlayerSaveGame, lockout: &LockoutData) -> i32 {
+    (lockout.Time() - save.TotalPlayTime()).max(0)
+}
+
+///
+/// Returns the running lockouts with the seconds of play left, expired ones are left out.
+///
+pub fn lockouts(save: &WillowTwoPlayerSaveGame) -> Vec<(&str, i32)> {
+    save.LockoutList
+        .iter()
+        .map(|lockout| (lockout.Lockout(), remaining_time(save, lockout)))
+        .filter(|(_, remaining)| *remaining > 0)
+        .collect()
+}
+
+///
+/// Removes a lockout so the encounter can be repeated right away.
+///
+pub fn reset_lockout(
+    save: &mut WillowTwoPlayerSaveGame,
+    lockout: &str,
+) -> Result<(), LockoutError> {
+    let count = save.LockoutList.len();
+    save.LockoutList.retain(|data| data.Lockout() != lockout);
+    if save.LockoutList.len() == count {
+        return Err(LockoutError::NoSuchLockout {
+            msg: format!("{} is not locked out!", lockout),
+        });
+    }
+    Ok(())
+}
+
+///
+/// Removes every lockout and returns how many there were.
+///
+pub fn reset_all_lockouts(save: &mut WillowTwoPlayerSaveGame) -> usize {
+    let count = save.LockoutList.len();
+    save.LockoutList.clear();
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lockouts, remaining_time, reset_all_lockouts, reset_lockout};
+    use crate::load_test_save;
+    use crate::LockoutData::LockoutData;
+
+    fn lockout(name: &str, time: i32) -> LockoutData {
+        let mut data = LockoutData::new();
+        data.set_Lockout(name.to_string());
+        data.set_Time(time);
+        data.set_IsFromDLC(false);
+        data.set_DLCPackageId(0);
+        data
+    }
+
+    #[test]
+    fn reset_lockout_test() {
+        let mut save_game = load_test_save();
+        assert!(lockouts(&save_game).is_empty());
+        let now = save_game.TotalPlayTime();
+        save_game
+            .LockoutList
+            .push(lockout("Terramorphous", now + 100));
+        save_game.LockoutList.push(lockout("Hyperius", now + 200));
+        save_game.LockoutList.push(lockout("Gee", now + 300));
+        save_game.LockoutList.push(lockout("Pyro Pete", now));
+
+        reset_lockout(&mut save_game, "Hyperius").unwrap();
+        assert!(reset_lockout(&mut save_game, "Hyperius").is_err());
+        assert_eq!(
+            lockouts(&save_game),
+            vec![("Terramorphous", 100), ("Gee", 300)]
+        );
+        assert_eq!(remaining_time(&save_game, &save_game.LockoutList[2]), 0);
+
+        let buffer = crate::write_save_mem(&save_game).unwrap();
+        let mut save_game = crate::load_save_mem(buffer).unwrap();
+        assert_eq!(reset_all_lockouts(&mut save_game), 3);
+        assert!(save_game.LockoutList.is_empty());
+    }
+}
@@ -0,0 +1,1211 @@
+use crate::playthrough::Playthrough;
+use crate::MissionData::MissionData;
+use crate::MissionPlaythroughData::MissionPlaythroughData;
+use crate::MissionStatus::MissionStatus;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+#[derive(Debug)]
+pub enum MissionError {
+    NoSuchPlaythrough { msg: String },
+    NoSuchMission { msg: String },
+    InvalidObjective { msg: String },
+}
+
+impl std::fmt::Display for MissionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MissionError::NoSuchPlaythrough { msg } => write!(f, "NoSuchPlaythrough: {}", msg),
+            MissionError::NoSuchMission { msg } => write!(f, "NoSuchMission: {}", msg),
+            MissionError::InvalidObjective { msg } => write!(f, "InvalidObjective: {}", msg),
+        }
+    }
+}
+
+///
+/// Entry of the embedded mission catalog.
+///
+#[derive(Debug)]
+pub struct MissionDefinition {
+    /// Full path as stored in `MissionData.Mission`.
+    pub name: &'static str,
+    pub display_name: &'static str,
+    /// Number of entries in `ObjectivesProgress`, 0 if it is not known.
+    pub objectives: usize,
+    /// Missions that have to be complete before this one can be started.
+    pub prerequisites: &'static [&'static str],
+    /// `DLCPackageId` of the mission, 0 for missions of the base game.
+    pub dlc_package_id: i32,
+    /// Whether the mission belongs to the main plot.
+    pub story: bool,
+}
+
+///
+/// Inconsistency between the tracked missions of a playthrough and the catalog.
+///
+#[derive(Debug, PartialEq, Eq)]
+pub enum MissionIssue {
+    PrerequisiteNotComplete {
+        mission: String,
+        prerequisite: String,
+    },
+    ObjectiveCount {
+        mission: String,
+        expected: usize,
+        found: usize,
+    },
+    UnknownMission {
+        mission: String,
+    },
+}
+
+impl std::fmt::Display for MissionIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MissionIssue::PrerequisiteNotComplete {
+                mission,
+                prerequisite,
+            } => write!(
+                f,
+                "PrerequisiteNotComplete: {} was started before {} was complete",
+                mission, prerequisite
+            ),
+            MissionIssue::ObjectiveCount {
+                mission,
+                expected,
+                found,
+            } => write!(
+                f,
+                "ObjectiveCount: {} has {} objectives instead of {}",
+                mission, found, expected
+            ),
+            MissionIssue::UnknownMission { mission } => {
+                write!(
+                    f,
+                    "UnknownMission: {} is not in the mission catalog",
+                    mission
+                )
+            }
+        }
+    }
+}
+
+///
+/// Returns the catalog entry of a mission.
+///
+pub fn mission_definition(name: &str) -> Option<&'static MissionDefinition> {
+    MISSIONS.iter().find(|definition| definition.name == name)
+}
+
+///
+/// Returns the whole mission catalog.
+///
+/// The catalog holds the base game missions tracked by the reference save. Base game side
+/// missions that save never picked up and all DLC missions are not part of it, such missions
+/// are reported as unknown by `mission_definition`. The reference save tracks no DLC mission,
+/// so there is no `DLCPackageId` to check DLC entries against.
+///
+pub fn mission_catalog() -> &'static [MissionDefinition] {
+    MISSIONS
+}
+
+///
+/// Returns the main plot missions in the order they are played.
+///
+pub fn story_missions() -> Vec<&'static MissionDefinition> {
+    MISSIONS
+        .iter()
+        .filter(|definition| definition.story)
+        .collect()
+}
+
+fn playthrough_data(
+    save: &WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<&MissionPlaythroughData, MissionError> {
+    save.MissionPlaythroughs
+        .get(playthrough.index())
+        .ok_or_else(|| MissionError::NoSuchPlaythrough {
+            msg: format!("The save has no missions for {:?}!", playthrough),
+        })
+}
+
+fn playthrough_data_mut(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<&mut MissionPlaythroughData, MissionError> {
+    save.MissionPlaythroughs
+        .get_mut(playthrough.index())
+        .ok_or_else(|| MissionError::NoSuchPlaythrough {
+            msg: format!("The save has no missions for {:?}!", playthrough),
+        })
+}
+
+fn mission_mut<'a>(
+    save: &'a mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+) -> Result<&'a mut MissionData, MissionError> {
+    playthrough_data_mut(save, playthrough)?
+        .MissionData
+        .iter_mut()
+        .find(|data| data.Mission() == mission)
+        .ok_or_else(|| MissionError::NoSuchMission {
+            msg: format!("{} is not tracked in {:?}!", mission, playthrough),
+        })
+}
+
+///
+/// Returns the missions tracked in a playthrough with their status.
+///
+pub fn missions(
+    save: &WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<Vec<(&str, MissionStatus)>, MissionError> {
+    Ok(playthrough_data(save, playthrough)?
+        .MissionData
+        .iter()
+        .map(|data| (data.Mission(), data.Status()))
+        .collect())
+}
+
+///
+/// Returns the data of a mission tracked in a playthrough.
+///
+pub fn mission<'a>(
+    save: &'a WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+) -> Option<&'a MissionData> {
+    playthrough_data(save, playthrough)
+        .ok()?
+        .MissionData
+        .iter()
+        .find(|data| data.Mission() == mission)
+}
+
+///
+/// Sets the status of a mission, missions that are not tracked yet are added.
+///
+/// Added missions take their `DLCPackageId` from the catalog or from another playthrough
+/// tracking the same mission. Other missions are refused, adding them as base game missions
+/// would break DLC missions. Completed missions no longer need their rewards to be handed out.
+///
+pub fn set_mission_status(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+    status: MissionStatus,
+) -> Result<(), MissionError> {
+    let dlc_package_id = match mission_definition(mission) {
+        Some(definition) => Some((definition.dlc_package_id != 0, definition.dlc_package_id)),
+        None => save
+            .MissionPlaythroughs
+            .iter()
+            .flat_map(|data| data.MissionData.iter())
+            .find(|data| data.Mission() == mission)
+            .map(|data| (data.IsFromDLC(), data.DLCPackageId())),
+    };
+    let data = playthrough_data_mut(save, playthrough)?;
+    let index = match data
+        .MissionData
+        .iter()
+        .position(|data| data.Mission() == mission)
+    {
+        Some(index) => index,
+        None => {
+            let (is_from_dlc, dlc_package_id) =
+                dlc_package_id.ok_or_else(|| MissionError::NoSuchMission {
+                    msg: format!(
+                        "{} is not in the mission catalog, its DLCPackageId is not known!",
+                        mission
+                    ),
+                })?;
+            let mut mission_data = MissionData::new();
+            mission_data.set_Mission(mission.to_string());
+            mission_data.set_IsFromDLC(is_from_dlc);
+            mission_data.set_DLCPackageId(dlc_package_id);
+            mission_data.set_ActiveObjectiveSetIndex(0);
+            mission_data.set_NeedsRewards(false);
+            mission_data.set_HeardKickoff(false);
+            mission_data.set_GameStage(0);
+            data.MissionData.push(mission_data);
+            data.MissionData.len() - 1
+        }
+    };
+    let mission_data = &mut data.MissionData[index];
+    mission_data.set_Status(status);
+    if status == MissionStatus::Complete {
+        mission_data.set_NeedsRewards(false);
+    }
+    Ok(())
+}
+
+///
+/// Sets the progress of an objective of the active objective set.
+///
+/// `ObjectivesProgress` is extended with zeros if it is too short.
+///
+pub fn set_objective_progress(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+    objective: usize,
+    progress: i32,
+) -> Result<(), MissionError> {
+    if progress < 0 {
+        return Err(MissionError::InvalidObjective {
+            msg: format!("Objective progress can not be {}!", progress),
+        });
+    }
+    let data = mission_mut(save, playthrough, mission)?;
+    if data.ObjectivesProgress.len() <= objective {
+        data.ObjectivesProgress.resize(objective + 1, 0);
+    }
+    data.ObjectivesProgress[objective] = progress;
+    Ok(())
+}
+
+///
+/// Sets the progress of all objectives at once, e.g. to the goals of every objective to
+/// complete them.
+///
+pub fn set_objectives(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+    progress: &[i32],
+) -> Result<(), MissionError> {
+    if let Some(invalid) = progress.iter().find(|progress| **progress < 0) {
+        return Err(MissionError::InvalidObjective {
+            msg: format!("Objective progress can not be {}!", invalid),
+        });
+    }
+    mission_mut(save, playthrough, mission)?.ObjectivesProgress = progress.to_vec();
+    Ok(())
+}
+
+///
+/// Selects the active objective set and the active sub objective sets of a mission.
+///
+pub fn set_objective_set(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+    active: i32,
+    sub_objective_sets: &[i32],
+) -> Result<(), MissionError> {
+    if active < 0 || sub_objective_sets.iter().any(|index| *index < 0) {
+        return Err(MissionError::InvalidObjective {
+            msg: format!(
+                "Objective set {} with sub sets {:?} is invalid!",
+                active, sub_objective_sets
+            ),
+        });
+    }
+    let data = mission_mut(save, playthrough, mission)?;
+    data.set_ActiveObjectiveSetIndex(active);
+    data.SubObjectiveSetIndexes = sub_objective_sets.to_vec();
+    Ok(())
+}
+
+///
+/// Zeroes the objective progress of a mission and returns to its first objective set.
+///
+pub fn reset_objectives(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+) -> Result<(), MissionError> {
+    let data = mission_mut(save, playthrough, mission)?;
+    data.ObjectivesProgress
+        .iter_mut()
+        .for_each(|progress| *progress = 0);
+    data.set_ActiveObjectiveSetIndex(0);
+    data.SubObjectiveSetIndexes.clear();
+    Ok(())
+}
+
+///
+/// Sets whether the rewards of a mission still have to be handed out.
+///
+pub fn set_needs_rewards(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+    needs_rewards: bool,
+) -> Result<(), MissionError> {
+    mission_mut(save, playthrough, mission)?.set_NeedsRewards(needs_rewards);
+    Ok(())
+}
+
+///
+/// Sets whether the kickoff dialog of a mission was heard.
+///
+pub fn set_heard_kickoff(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+    heard_kickoff: bool,
+) -> Result<(), MissionError> {
+    mission_mut(save, playthrough, mission)?.set_HeardKickoff(heard_kickoff);
+    Ok(())
+}
+
+///
+/// Returns the mission tracked in the HUD of a playthrough.
+///
+pub fn active_mission(
+    save: &WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<&str, MissionError> {
+    Ok(playthrough_data(save, playthrough)?.ActiveMission())
+}
+
+///
+/// Sets the mission tracked in the HUD of a playthrough, it has to be tracked already.
+///
+pub fn set_active_mission(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+) -> Result<(), MissionError> {
+    mission_mut(save, playthrough, mission)?;
+    playthrough_data_mut(save, playthrough)?.set_ActiveMission(mission.to_string());
+    Ok(())
+}
+
+///
+/// Checks the tracked missions of a playthrough against the catalog.
+///
+/// Started missions need all prerequisites complete and objective progress has to match the
+/// objective count. Missions missing from the catalog are reported as unknown.
+///
+pub fn validate_missions(
+    save: &WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<Vec<MissionIssue>, MissionError> {
+    let data = playthrough_data(save, playthrough)?;
+    let status = |name: &str| {
+        data.MissionData
+            .iter()
+            .find(|data| data.Mission() == name)
+            .map(|data| data.Status())
+            .unwrap_or(MissionStatus::NotStarted)
+    };
+    let mut issues = Vec::new();
+    for mission in data
+        .MissionData
+        .iter()
+        .filter(|mission| mission.Status() != MissionStatus::NotStarted)
+    {
+        let definition = match mission_definition(mission.Mission()) {
+            Some(definition) => definition,
+            None => {
+                issues.push(MissionIssue::UnknownMission {
+                    mission: mission.Mission().to_string(),
+                });
+                continue;
+            }
+        };
+        for prerequisite in definition.prerequisites {
+            if status(prerequisite) != MissionStatus::Complete {
+                issues.push(MissionIssue::PrerequisiteNotComplete {
+                    mission: definition.name.to_string(),
+                    prerequisite: prerequisite.to_string(),
+                });
+            }
+        }
+        let found = mission.ObjectivesProgress.len();
+        if definition.objectives > 0 && found > 0 && found != definition.objectives {
+            issues.push(MissionIssue::ObjectiveCount {
+                mission: definition.name.to_string(),
+                expected: definition.objectives,
+                found,
+            });
+        }
+    }
+    Ok(issues)
+}
+
+const fn story(
+    name: &'static str,
+    display_name: &'static str,
+    objectives: usize,
+    prerequisites: &'static [&'static str],
+) -> MissionDefinition {
+    MissionDefinition {
+        name,
+        display_name,
+        objectives,
+        prerequisites,
+        dlc_package_id: 0,
+        story: true,
+    }
+}
+
+const fn side(
+    name: &'static str,
+    display_name: &'static str,
+    objectives: usize,
+    prerequisites: &'static [&'static str],
+) -> MissionDefinition {
+    MissionDefinition {
+        name,
+        display_name,
+        objectives,
+        prerequisites,
+        dlc_package_id: 0,
+        story: false,
+    }
+}
+
+static MISSIONS: &[MissionDefinition] = &[
+    story("GD_Episode01.M_Ep1_Champion", "My First Gun", 1, &[]),
+    story(
+        "GD_Episode02.M_Ep2_Henchman",
+        "Blindsided",
+        9,
+        &["GD_Episode01.M_Ep1_Champion"],
+    ),
+    story(
+        "GD_Episode02.M_Ep2a_MoreGuns",
+        "Cleaning Up the Berg",
+        7,
+        &["GD_Episode02.M_Ep2_Henchman"],
+    ),
+    side(
+        "GD_Z1_ThisTown.M_ThisTown",
+        "This Town Ain't Big Enough",
+        2,
+        &["GD_Episode02.M_Ep2_Henchman"],
+    ),
+    story(
+        "GD_Episode02.M_Ep2c_Henchman",
+        "Best Minion Ever",
+        14,
+        &["GD_Episode02.M_Ep2a_MoreGuns"],
+    ),
+    story(
+        "GD_Episode02.M_Ep2b_Henchman",
+        "Best Minion Ever (Part 2)",
+        7,
+        &["GD_Episode02.M_Ep2c_Henchman"],
+    ),
+    side(
+        "GD_Z1_BadHairDay.M_BadHairDay",
+        "Bad Hair Day",
+        3,
+        &["GD_Episode02.M_Ep2c_Henchman"],
+    ),
+    side(
+        "GD_Z1_HandsomeJackHere.M_HandsomeJackHere",
+        "Handsome Jack Here!",
+        3,
+        &["GD_Episode02.M_Ep2c_Henchman"],
+    ),
+    side(
+        "GD_Z1_Symbiosis.M_Symbiosis",
+        "Symbiosis",
+        3,
+        &["GD_Episode02.M_Ep2c_Henchman"],
+    ),
+    story(
+        "GD_Episode03.M_Ep3_CatchARide",
+        "The Road to Sanctuary",
+        14,
+        &["GD_Episode02.M_Ep2b_Henchman"],
+    ),
+    story(
+        "GD_Episode04.M_Ep4_WelcomeToSanctuary",
+        "Plan B",
+        12,
+        &["GD_Episode03.M_Ep3_CatchARide"],
+    ),
+    side(
+        "GD_Z1_NameGame.M_NameGame",
+        "The Name Game",
+        5,
+        &["GD_Episode03.M_Ep3_CatchARide"],
+    ),
+    side(
+        "GD_Z1_Assasinate.M_AssasinateTheAssassins",
+        "Assassinate the Assassins",
+        12,
+        &["GD_Episode03.M_Ep3_CatchARide"],
+    ),
+    side(
+        "GD_Z1_RockPaperGenocide.M_RockPaperGenocide_Fire",
+        "Rock, Paper, Genocide: Fire Weapons!",
+        2,
+        &["GD_Episode03.M_Ep3_CatchARide"],
+    ),
+    side(
+        "GD_Z1_RockPaperGenocide.M_RockPaperGenocide_Shock",
+        "Rock, Paper, Genocide: Shock Weapons!",
+        1,
+        &["GD_Episode03.M_Ep3_CatchARide"],
+    ),
+    side(
+        "GD_Z1_RockPaperGenocide.M_RockPaperGenocide_Corrosive",
+        "Rock, Paper, Genocide: Corrosive Weapons!",
+        1,
+        &["GD_Episode03.M_Ep3_CatchARide"],
+    ),
+    side(
+        "GD_Z1_RockPaperGenocide.M_RockPaperGenocide_Amp",
+        "Rock, Paper, Genocide: Slag Weapons!",
+        2,
+        &["GD_Episode03.M_Ep3_CatchARide"],
+    ),
+    story(
+        "GD_Episode05.M_Ep5_ThePhoenix",
+        "Hunting the Firehawk",
+        10,
+        &["GD_Episode04.M_Ep4_WelcomeToSanctuary"],
+    ),
+    side(
+        "GD_Z1_ClapTrapStash.M_ClapTrapStash",
+        "Claptrap's Secret Stash",
+        7,
+        &["GD_Episode04.M_Ep4_WelcomeToSanctuary"],
+    ),
+    side(
+        "GD_Z1_Surgery.M_PerformSurgery",
+        "Do No Harm",
+        3,
+        &["GD_Episode04.M_Ep4_WelcomeToSanctuary"],
+    ),
+    side(
+        "GD_Z3_MedicalMystery.M_MedicalMystery",
+        "Medical Mystery",
+        4,
+        &["GD_Episode04.M_Ep4_WelcomeToSanctuary"],
+    ),
+    side(
+        "GD_Z1_NoVacancy.BalanceDefs.M_NoVacancy",
+        "No Vacancy",
+        12,
+        &["GD_Episode04.M_Ep4_WelcomeToSanctuary"],
+    ),
+    side(
+        "gd_z3_neitherrainsleet.M_NeitherRainSleetSkags",
+        "Neither Rain nor Sleet nor Skags",
+        2,
+        &["GD_Episode04.M_Ep4_WelcomeToSanctuary"],
+    ),
+    side(
+        "GD_Z3_MedicalMystery2.M_MedicalMystery2",
+        "Medical Mystery: X-Com-municate",
+        1,
+        &["GD_Episode04.M_Ep4_WelcomeToSanctuary"],
+    ),
+    story(
+        "GD_Episode06.M_Ep6_RescueRoland",
+        "A Dam Fine Rescue",
+        19,
+        &["GD_Episode05.M_Ep5_ThePhoenix"],
+    ),
+    side(
+        "GD_Z1_ChildrenOfPhoenix.M_EternalFlame",
+        "Cult Following: Eternal Flame",
+        2,
+        &["GD_Episode05.M_Ep5_ThePhoenix"],
+    ),
+    side(
+        "GD_Z1_InMemoriam.M_InMemoriam",
+        "In Memoriam",
+        5,
+        &["GD_Episode05.M_Ep5_ThePhoenix"],
+    ),
+    side(
+        "GD_Z1_ChildrenOfPhoenix.M_FalseIdols",
+        "Cult Following: False Idols",
+        1,
+        &["GD_Episode05.M_Ep5_ThePhoenix"],
+    ),
+    side(
+        "GD_Z1_ChildrenOfPhoenix.M_LightingTheMatch",
+        "Cult Following: Lighting the Match",
+        6,
+        &["GD_Episode05.M_Ep5_ThePhoenix"],
+    ),
+    side(
+        "GD_Z1_ChildrenOfPhoenix.M_TheEnkindling",
+        "Cult Following: The Enkindling",
+        5,
+        &["GD_Episode05.M_Ep5_ThePhoenix"],
+    ),
+    side(
+        "GD_Z3_PositiveSelfImage.M_PositiveSelfImage",
+        "Positive Self Image",
+        2,
+        &["GD_Episode05.M_Ep5_ThePhoenix"],
+    ),
+    side(
+        "gd_z3_toocloseformissiles.M_TooCloseForMissiles",
+        "Too Close for Missiles",
+        8,
+        &["GD_Episode05.M_Ep5_ThePhoenix"],
+    ),
+    story(
+        "GD_Episode07.M_Ep7_ATrainToCatch",
+        "A Train to Catch",
+        19,
+        &["GD_Episode06.M_Ep6_RescueRoland"],
+    ),
+    side(
+        "GD_Z2_SplinterGroup.M_SplinterGroup",
+        "Splinter Group",
+        5,
+        &["GD_Episode06.M_Ep6_RescueRoland"],
+    ),
+    side(
+        "GD_Z1_MightyMorphin.M_MightyMorphin",
+        "Mighty Morphin'",
+        7,
+        &["GD_Episode06.M_Ep6_RescueRoland"],
+    ),
+    side(
+        "GD_Z3_OutOfBody.M_OutOfBody",
+        "Out of Body Experience",
+        11,
+        &["GD_Episode06.M_Ep6_RescueRoland"],
+    ),
+    side(
+        "gd_z1_nohardfeelings.M_NoHardFeelings",
+        "No Hard Feelings",
+        3,
+        &["GD_Episode06.M_Ep6_RescueRoland"],
+    ),
+    side(
+        "GD_Z1_CordiallyInvited.M_CordiallyInvited",
+        "You Are Cordially Invited: Party Prep",
+        7,
+        &["GD_Episode06.M_Ep6_RescueRoland"],
+    ),
+    story(
+        "GD_Episode08.M_Ep8_SanctuaryTakesOff",
+        "Rising Action",
+        11,
+        &["GD_Episode07.M_Ep7_ATrainToCatch"],
+    ),
+    story(
+        "GD_Episode09.M_Ep9_GetBackToSanctuary",
+        "Bright Lights, Flying City",
+        14,
+        &["GD_Episode08.M_Ep8_SanctuaryTakesOff"],
+    ),
+    side(
+        "GD_Z1_WontGetFooled.M_WontGetFooled",
+        "Won't Get Fooled Again",
+        4,
+        &["GD_Episode08.M_Ep8_SanctuaryTakesOff"],
+    ),
+    side(
+        "GD_Z2_SafeAndSound.M_SafeAndSound",
+        "Safe and Sound",
+        7,
+        &["GD_Episode08.M_Ep8_SanctuaryTakesOff"],
+    ),
+    side(
+        "GD_Z3_SwallowedWhole.M_SwallowedWhole",
+        "Swallowed Whole",
+        4,
+        &["GD_Episode08.M_Ep8_SanctuaryTakesOff"],
+    ),
+    side(
+        "GD_Z3_ColdShoulder.M_ColdShoulder",
+        "The Cold Shoulder",
+        5,
+        &["GD_Episode08.M_Ep8_SanctuaryTakesOff"],
+    ),
+    side(
+        "GD_Z3_GoodBadMordecai.M_GoodBadMordecai",
+        "The Good, the Bad, and the Mordecai",
+        8,
+        &["GD_Episode08.M_Ep8_SanctuaryTakesOff"],
+    ),
+    side(
+        "GD_Z2_MeetWithEllie.M_MeetWithEllie",
+        "Meet with Ellie",
+        11,
+        &["GD_Episode08.M_Ep8_SanctuaryTakesOff"],
+    ),
+    side(
+        "GD_Z1_PerfectlyPeaceful.M_PerfectlyPeaceful",
+        "Perfectly Peaceful",
+        4,
+        &["GD_Episode08.M_Ep8_SanctuaryTakesOff"],
+    ),
+    story(
+        "GD_Episode10.M_Ep10_BirdISTheWord",
+        "Wildlife Exploitation Preserve",
+        11,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z3_HiddenJournals.M_HiddenJournals",
+        "Hidden Journals",
+        1,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z2_DoctorsOrders.M_DoctorsOrders",
+        "Doctor's Orders",
+        1,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z1_MineAllMine.M_MineAllMine",
+        "Mine, All Mine",
+        5,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z2_ClaptrapBirthdayBash.M_ClaptrapBirthdayBash",
+        "Claptrap's Birthday Bash!",
+        9,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z2_Overlooked.M_Overlooked",
+        "The Overlooked: Medicine Man",
+        7,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "gd_z1_minecartmischief.M_MinecartMischief",
+        "Minecart Mischief",
+        9,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z1_TrainRobbery.M_TrainRobbery",
+        "The Pretty Good Train Robbery",
+        4,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z1_CordiallyInvited.M_CordiallyInvited02",
+        "You Are Cordially Invited: RSVP",
+        0,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z2_ArmsDealer.M_ArmsDealer",
+        "Arms Dealing",
+        4,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z2_TaggartBiography.M_TaggartBiography",
+        "Taggart Biography",
+        2,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z2_Overlooked2.M_Overlooked2",
+        "The Overlooked: Shields Up",
+        3,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    side(
+        "GD_Z2_Overlooked3.M_Overlooked3",
+        "The Overlooked: This Is Only a Test",
+        6,
+        &["GD_Episode09.M_Ep9_GetBackToSanctuary"],
+    ),
+    story(
+        "GD_Episode11.M_Ep11_LikeATonOf",
+        "The Once and Future Slab",
+        7,
+        &["GD_Episode10.M_Ep10_BirdISTheWord"],
+    ),
+    side(
+        "GD_Z2_FreeWilly.M_FreeWilly",
+        "Animal Rights",
+        7,
+        &["GD_Episode10.M_Ep10_BirdISTheWord"],
+    ),
+    side(
+        "GD_Z2_Rakkaholics.M_Rakkaholics",
+        "Rakkaholics Anonymous",
+        3,
+        &["GD_Episode10.M_Ep10_BirdISTheWord"],
+    ),
+    side(
+        "GD_Z2_PoeticLicense.M_PoeticLicense",
+        "Poetic License",
+        6,
+        &["GD_Episode10.M_Ep10_BirdISTheWord"],
+    ),
+    side(
+        "GD_Z1_ShootMeInTheFace.M_ShootMeInTheFace",
+        "Shoot This Guy in the Face",
+        4,
+        &["GD_Episode10.M_Ep10_BirdISTheWord"],
+    ),
+    story(
+        "GD_Episode12.M_Ep12_BecomingJack",
+        "The Man Who Would Be Jack",
+        7,
+        &["GD_Episode11.M_Ep11_LikeATonOf"],
+    ),
+    side(
+        "GD_Z2_RockosModernStrife.M_RockosModernStrife",
+        "Rocko's Modern Strife",
+        1,
+        &["GD_Episode11.M_Ep11_LikeATonOf"],
+    ),
+    side(
+        "GD_Z2_HomeMovies.M_HomeMovies",
+        "Home Movies",
+        12,
+        &["GD_Episode11.M_Ep11_LikeATonOf"],
+    ),
+    side(
+        "GD_Z2_HellHathNo.M_FloodingHyperionCity",
+        "Hell Hath No Fury",
+        7,
+        &["GD_Episode11.M_Ep11_LikeATonOf"],
+    ),
+    side(
+        "GD_Z3_Bane.M_Bane",
+        "The Bane",
+        5,
+        &["GD_Episode11.M_Ep11_LikeATonOf"],
+    ),
+    side(
+        "GD_Z2_HyperionStatue.M_MonumentsVandalism",
+        "Monumental Vandalism",
+        0,
+        &["GD_Episode11.M_Ep11_LikeATonOf"],
+    ),
+    side(
+        "GD_Z2_WrittenByVictor.M_WrittenByVictor",
+        "Written by the Victor",
+        6,
+        &["GD_Episode11.M_Ep11_LikeATonOf"],
+    ),
+    story(
+        "GD_Episode13.M_Ep13_KillAngel",
+        "Where Angels Fear to Tread",
+        11,
+        &["GD_Episode12.M_Ep12_BecomingJack"],
+    ),
+    side(
+        "GD_Z2_TheBankJob.M_TheBankJob",
+        "Breaking the Bank",
+        12,
+        &["GD_Episode12.M_Ep12_BecomingJack"],
+    ),
+    side(
+        "GD_Z2_BlowTheBridge.M_BlowTheBridge",
+        "Blow the Bridge",
+        6,
+        &["GD_Episode12.M_Ep12_BecomingJack"],
+    ),
+    side(
+        "GD_Z2_Skagzilla2.M_Skagzilla2_Pup",
+        "Hungry Like the Skag: Pup",
+        5,
+        &["GD_Episode12.M_Ep12_BecomingJack"],
+    ),
+    side(
+        "GD_Z2_Skagzilla2.M_Skagzilla2_Adult",
+        "Hungry Like the Skag: Adult",
+        2,
+        &["GD_Episode12.M_Ep12_BecomingJack"],
+    ),
+    side(
+        "GD_Z2_Skagzilla2.M_Skagzilla2_Den",
+        "Hungry Like the Skag: Den",
+        3,
+        &["GD_Episode12.M_Ep12_BecomingJack"],
+    ),
+    side(
+        "GD_Z2_KillTheSheriff.M_KillTheSheriff",
+        "Showdown",
+        4,
+        &["GD_Episode12.M_Ep12_BecomingJack"],
+    ),
+    story(
+        "GD_Episode14.M_Ep14_SearchingTheWreckage",
+        "Where Angels Fear to Tread (Part 2)",
+        1,
+        &["GD_Episode13.M_Ep13_KillAngel"],
+    ),
+    story(
+        "GD_Episode16.M_Ep16_LockAndLoad",
+        "Toil and Trouble",
+        15,
+        &["GD_Episode14.M_Ep14_SearchingTheWreckage"],
+    ),
+    side(
+        "GD_Z3_CaptureTheFlags.M_CaptureTheFlags",
+        "Capture the Flags",
+        12,
+        &["GD_Episode14.M_Ep14_SearchingTheWreckage"],
+    ),
+    side(
+        "GD_Z1_BearerBadNews.M_BearerBadNews",
+        "Bearer of Bad News",
+        8,
+        &["GD_Episode14.M_Ep14_SearchingTheWreckage"],
+    ),
+    side(
+        "GD_Z3_ChosenOne.M_ChosenOne",
+        "The Chosen One",
+        3,
+        &["GD_Episode14.M_Ep14_SearchingTheWreckage"],
+    ),
+    side(
+        "GD_Z3_MonsterMash1.M_MonsterMash1",
+        "Monster Mash (Part 1)",
+        1,
+        &["GD_Episode14.M_Ep14_SearchingTheWreckage"],
+    ),
+    side(
+        "GD_Z2_DemonHunter.M_DemonHunter",
+        "Demon Hunter",
+        2,
+        &["GD_Episode14.M_Ep14_SearchingTheWreckage"],
+    ),
+    side(
+        "GD_Z1_BFFs.M_BFFs",
+        "BFFs",
+        5,
+        &["GD_Episode14.M_Ep14_SearchingTheWreckage"],
+    ),
+    side(
+        "GD_Z3_GreatEscape.M_GreatEscape",
+        "The Great Escape",
+        3,
+        &["GD_Episode14.M_Ep14_SearchingTheWreckage"],
+    ),
+    story(
+        "GD_Episode15.M_Ep15_CharacterAssassination",
+        "The Talon of God",
+        14,
+        &["GD_Episode16.M_Ep16_LockAndLoad"],
+    ),
+    side(
+        "GD_Z3_YouDontKnowJack.M_YouDontKnowJack",
+        "You Don't Know Jack",
+        5,
+        &["GD_Episode16.M_Ep16_LockAndLoad"],
+    ),
+    side(
+        "GD_Z3_UncleTeddy.M_UncleTeddy",
+        "Uncle Teddy",
+        6,
+        &["GD_Episode16.M_Ep16_LockAndLoad"],
+    ),
+    story(
+        "GD_Episode17.M_Ep17_KillJack",
+        "The Talon of God (Part 2)",
+        18,
+        &["GD_Episode15.M_Ep15_CharacterAssassination"],
+    ),
+    side(
+        "GD_Z1_BanditSlaughter.M_BanditSlaughter1",
+        "Bandit Slaughter: Round 1",
+        5,
+        &["GD_Episode15.M_Ep15_CharacterAssassination"],
+    ),
+    side(
+        "GD_Z2_ThresherRaid.M_ThresherRaid",
+        "You. Will. Die. (Seriously.)",
+        4,
+        &["GD_Episode15.M_Ep15_CharacterAssassination"],
+    ),
+    side(
+        "GD_Z3_ThisJustIn.M_ThisJustIn",
+        "This Just In",
+        2,
+        &["GD_Episode15.M_Ep15_CharacterAssassination"],
+    ),
+    side(
+        "GD_Z1_BanditSlaughter.M_BanditSlaughter2",
+        "Bandit Slaughter: Round 2",
+        5,
+        &["GD_Z1_BanditSlaughter.M_BanditSlaughter1"],
+    ),
+    side(
+        "GD_Z1_BanditSlaughter.M_BanditSlaughter3",
+        "Bandit Slaughter: Round 3",
+        5,
+        &["GD_Z1_BanditSlaughter.M_BanditSlaughter2"],
+    ),
+    side(
+        "GD_Z1_BanditSlaughter.M_BanditSlaughter4",
+        "Bandit Slaughter: Round 4",
+        5,
+        &["GD_Z1_BanditSlaughter.M_BanditSlaughter3"],
+    ),
+    side(
+        "GD_Z1_BanditSlaughter.M_BanditSlaughter5",
+        "Bandit Slaughter: Round 5",
+        5,
+        &["GD_Z1_BanditSlaughter.M_BanditSlaughter4"],
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        active_mission, mission, mission_definition, missions, reset_objectives,
+        set_active_mission, set_mission_status, set_objective_progress, story_missions,
+        validate_missions, MissionError, MissionIssue,
+    };
+    use crate::load_test_save;
+    use crate::playthrough::Playthrough;
+    use crate::MissionStatus::MissionStatus;
+
+    #[test]
+    fn mission_catalog_test() {
+        let save_game = load_test_save();
+        for data in save_game
+            .MissionPlaythroughs
+            .iter()
+            .flat_map(|playthrough| playthrough.MissionData.iter())
+        {
+            let definition = mission_definition(data.Mission()).unwrap();
+            assert_eq!(definition.dlc_package_id, data.DLCPackageId());
+        }
+    }
+
+    #[test]
+    fn mission_status_test() {
+        let mut save_game = load_test_save();
+        let tracked = missions(&save_game, Playthrough::Normal).unwrap();
+        assert_eq!(tracked.len(), 99);
+        assert_eq!(
+            tracked[0],
+            ("GD_Episode01.M_Ep1_Champion", MissionStatus::Complete)
+        );
+        assert!(missions(&save_game, Playthrough::UltimateVaultHunter)
+            .unwrap()
+            .is_empty());
+
+        set_mission_status(
+            &mut save_game,
+            Playthrough::UltimateVaultHunter,
+            "GD_Episode01.M_Ep1_Champion",
+            MissionStatus::Active,
+        )
+        .unwrap();
+        set_active_mission(
+            &mut save_game,
+            Playthrough::UltimateVaultHunter,
+            "GD_Episode01.M_Ep1_Champion",
+        )
+        .unwrap();
+        assert_eq!(
+            active_mission(&save_game, Playthrough::UltimateVaultHunter).unwrap(),
+            "GD_Episode01.M_Ep1_Champion"
+        );
+        assert!(matches!(
+            set_active_mission(
+                &mut save_game,
+                Playthrough::UltimateVaultHunter,
+                "GD_Episode02.M_Ep2_Henchman"
+            ),
+            Err(MissionError::NoSuchMission { .. })
+        ));
+    }
+
+    #[test]
+    fn objectives_test() {
+        let mut save_game = load_test_save();
+        let name = "GD_Episode02.M_Ep2_Henchman";
+        set_objective_progress(&mut save_game, Playthrough::Normal, name, 10, 3).unwrap();
+        let data = mission(&save_game, Playthrough::Normal, name).unwrap();
+        assert_eq!(
+            data.ObjectivesProgress,
+            vec![1, 1, 1, 1, 1, 1, 0, 1, 1, 0, 3]
+        );
+
+        reset_objectives(&mut save_game, Playthrough::Normal, name).unwrap();
+        let data = mission(&save_game, Playthrough::Normal, name).unwrap();
+        assert!(data
+            .ObjectivesProgress
+            .iter()
+            .all(|progress| *progress == 0));
+        assert_eq!(data.ActiveObjectiveSetIndex(), 0);
+        assert!(set_objective_progress(&mut save_game, Playthrough::Normal, name, 0, -1).is_err());
+    }
+
+    #[test]
+    fn validate_missions_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(story_missions().len(), 20);
+        assert!(validate_missions(&save_game, Playthrough::Normal)
+            .unwrap()
+            .is_empty());
+        assert!(validate_missions(&save_game, Playthrough::TrueVaultHunter)
+            .unwrap()
+            .is_empty());
+
+        let name = "GD_Episode17.M_Ep17_KillJack";
+        set_mission_status(
+            &mut save_game,
+            Playthrough::UltimateVaultHunter,
+            name,
+            MissionStatus::Complete,
+        )
+        .unwrap();
+        set_objective_progress(&mut save_game, Playthrough::Normal, name, 20, 1).unwrap();
+        assert_eq!(
+            validate_missions(&save_game, Playthrough::UltimateVaultHunter).unwrap(),
+            vec![MissionIssue::PrerequisiteNotComplete {
+                mission: name.to_string(),
+                prerequisite: "GD_Episode15.M_Ep15_CharacterAssassination".to_string(),
+            }]
+        );
+        assert_eq!(
+            validate_missions(&save_game, Playthrough::Normal).unwrap(),
+            vec![MissionIssue::ObjectiveCount {
+                mission: name.to_string(),
+                expected: 18,
+                found: 21,
+            }]
+        );
+    }
+
+    #[test]
+    fn unknown_mission_test() {
+        let mut save_game = load_test_save();
+        let name = "GD_Unknown.M_Unknown";
+        assert!(matches!(
+            set_mission_status(
+                &mut save_game,
+                Playthrough::UltimateVaultHunter,
+                name,
+                MissionStatus::Active,
+            ),
+            Err(MissionError::NoSuchMission { .. })
+        ));
+
+        let data = &mut save_game.MissionPlaythroughs[0].MissionData[0];
+        data.set_Mission(name.to_string());
+        data.set_IsFromDLC(true);
+        data.set_DLCPackageId(3);
+        set_mission_status(
+            &mut save_game,
+            Playthrough::UltimateVaultHunter,
+            name,
+            MissionStatus::Active,
+        )
+        .unwrap();
+        let data = mission(&save_game, Playthrough::UltimateVaultHunter, name).unwrap();
+        assert!(data.IsFromDLC());
+        assert_eq!(data.DLCPackageId(), 3);
+        assert_eq!(
+            validate_missions(&save_game, Playthrough::UltimateVaultHunter).unwrap(),
+            vec![MissionIssue::UnknownMission {
+                mission: name.to_string(),
+            }]
+        );
+    }
+}
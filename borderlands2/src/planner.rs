@@ -0,0 +1,306 @@
+use crate::experience;
+use crate::skills::{self, ClassSkills, SkillTree};
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+#[derive(Debug)]
+pub enum PlannerError {
+    ParsingError { msg: String },
+    UnknownClass { msg: String },
+    InvalidBuild { msg: String },
+    NotEnoughSkillPoints { msg: String },
+}
+
+impl std::fmt::Display for PlannerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PlannerError::ParsingError { msg } => write!(f, "ParsingError: {}", msg),
+            PlannerError::UnknownClass { msg } => write!(f, "UnknownClass: {}", msg),
+            PlannerError::InvalidBuild { msg } => write!(f, "InvalidBuild: {}", msg),
+            PlannerError::NotEnoughSkillPoints { msg } => {
+                write!(f, "NotEnoughSkillPoints: {}", msg)
+            }
+        }
+    }
+}
+
+///
+/// Skill ranks of a class as planned with a skill calculator.
+///
+#[derive(Debug)]
+pub struct SkillBuild {
+    pub class: &'static ClassSkills,
+    /// One rank per skill, tree after tree in the order of `ClassSkills.trees`.
+    pub ranks: Vec<i32>,
+}
+
+impl SkillBuild {
+    ///
+    /// Returns the number of skill points the build spends.
+    ///
+    pub fn points(&self) -> i32 {
+        self.ranks.iter().sum()
+    }
+
+    fn tree_ranks(&self) -> Vec<(&'static SkillTree, &[i32])> {
+        let mut ranks = &self.ranks[..];
+        let mut trees = Vec::new();
+        for tree in self.class.trees {
+            let (tree_ranks, rest) = ranks.split_at(tree.skills.len());
+            trees.push((tree, tree_ranks));
+            ranks = rest;
+        }
+        trees
+    }
+}
+
+///
+/// Returns the key of a class in planner strings, the lower case class name.
+///
+pub fn class_key(class: &ClassSkills) -> String {
+    class.name.to_lowercase()
+}
+
+fn validate(build: &SkillBuild) -> Result<(), PlannerError> {
+    for (tree, ranks) in build.tree_ranks() {
+        for (skill, rank) in tree.skills.iter().zip(ranks) {
+            if *rank > skill.max_rank {
+                return Err(PlannerError::InvalidBuild {
+                    msg: format!(
+                        "{} has ranks 0 to {}, not {}!",
+                        skill.name, skill.max_rank, rank
+                    ),
+                });
+            }
+            let below: i32 = tree
+                .skills
+                .iter()
+                .zip(ranks)
+                .filter(|(other, _)| other.tier < skill.tier)
+                .map(|(_, rank)| rank)
+                .sum();
+            if *rank > 0 && below < skills::tier_threshold(skill.tier) {
+                return Err(PlannerError::InvalidBuild {
+                    msg: format!(
+                        "{} is in tier {} of {} which is not unlocked!",
+                        skill.name, skill.tier, tree.name
+                    ),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+///
+/// Parses a build in the skill planner format `<class>#<ranks>`.
+///
+/// The class is the lower case class name, full planner URLs like
+/// `https://bl2skills.com/assassin.html#...` are accepted as well. The ranks are one digit
+/// per skill, tree after tree, dashes between the trees are optional.
+///
+pub fn parse_build(build: &str) -> Result<SkillBuild, PlannerError> {
+    let (class, ranks) =
+        build
+            .trim()
+            .split_once('#')
+            .ok_or_else(|| PlannerError::ParsingError {
+                msg: format!("{} has no # between class and ranks!", build),
+            })?;
+    let key = class.rsplit('/').next().unwrap_or(class);
+    let key = key.strip_suffix(".html").unwrap_or(key).to_lowercase();
+    let class = skills::classes()
+        .iter()
+        .find(|class| class_key(class) == key)
+        .ok_or_else(|| PlannerError::UnknownClass {
+            msg: format!("{} is not a known class!", key),
+        })?;
+
+    let ranks = ranks
+        .chars()
+        .filter(|c| *c != '-')
+        .map(|c| {
+            c.to_digit(10)
+                .map(|digit| digit as i32)
+                .ok_or_else(|| PlannerError::ParsingError {
+                    msg: format!("{} is not a skill rank!", c),
+                })
+        })
+        .collect::<Result<Vec<i32>, PlannerError>>()?;
+    let skills: usize = class.trees.iter().map(|tree| tree.skills.len()).sum();
+    if ranks.len() != skills {
+        return Err(PlannerError::ParsingError {
+            msg: format!(
+                "A {} build has {} ranks, not {}!",
+                class.name,
+                skills,
+                ranks.len()
+            ),
+        });
+    }
+
+    let build = SkillBuild { class, ranks };
+    validate(&build)?;
+    Ok(build)
+}
+
+///
+/// Formats a build in the skill planner format, see `parse_build`.
+///
+pub fn format_build(build: &SkillBuild) -> String {
+    let trees: Vec<String> = build
+        .tree_ranks()
+        .iter()
+        .map(|(_, ranks)| ranks.iter().map(|rank| rank.to_string()).collect())
+        .collect();
+    format!("{}#{}", class_key(build.class), trees.join("-"))
+}
+
+///
+/// Returns the current skill allocation of a save as a build.
+///
+pub fn export_build(save: &WillowTwoPlayerSaveGame) -> Result<SkillBuild, PlannerError> {
+    let class =
+        skills::class_skills(save.PlayerClass()).ok_or_else(|| PlannerError::UnknownClass {
+            msg: format!("{} has no known skill trees!", save.PlayerClass()),
+        })?;
+    let ranks = class
+        .trees
+        .iter()
+        .flat_map(|tree| tree.skills.iter())
+        .map(|skill| skills::skill_rank(save, skill.name))
+        .collect();
+    Ok(SkillBuild { class, ranks })
+}
+
+///
+/// Respecs a save and spends its skill points on a build.
+///
+/// The build has to be for the class of the save. The action skill is always learned, so the
+/// build and the action skill together must not spend more skill points than the character
+/// has earned.
+///
+pub fn apply_build(
+    save: &mut WillowTwoPlayerSaveGame,
+    build: &SkillBuild,
+) -> Result<(), PlannerError> {
+    if build.class.class != save.PlayerClass() {
+        return Err(PlannerError::InvalidBuild {
+            msg: format!(
+                "A {} build can not be applied to {}!",
+                build.class.name,
+                save.PlayerClass()
+            ),
+        });
+    }
+    validate(build)?;
+    let earned = experience::skill_points_for_level(save.ExpLevel());
+    let needed = build.points() + 1;
+    if needed > earned {
+        return Err(PlannerError::NotEnoughSkillPoints {
+            msg: format!(
+                "The build and the action skill need {} skill points, level {} earns {}!",
+                needed,
+                save.ExpLevel(),
+                earned
+            ),
+        });
+    }
+
+    let mut working = save.clone();
+    skills::respec(&mut working);
+    skills::set_grade(&mut working, build.class.action_skill, 1);
+    for (tree, ranks) in build.tree_ranks() {
+        for (skill, rank) in tree.skills.iter().zip(ranks) {
+            if *rank > 0 {
+                skills::set_grade(&mut working, skill.name, *rank);
+            }
+        }
+    }
+    working.set_GeneralSkillPoints(earned - needed);
+    *save = working;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_build, export_build, format_build, parse_build, PlannerError};
+    use crate::load_test_save;
+    use crate::skills;
+
+    #[test]
+    fn export_build_test() {
+        let save_game = load_test_save();
+        let build = export_build(&save_game).unwrap();
+        let formatted = format_build(&build);
+        assert_eq!(formatted, "assassin#5255411351-0000000000-0000000000");
+
+        let parsed =
+            parse_build("https://bl2skills.com/assassin.html#525541135100000000000000000000")
+                .unwrap();
+        assert_eq!(parsed.ranks, build.ranks);
+        assert!(matches!(
+            parse_build("assassin#0050000000-0000000000-0000000000"),
+            Err(PlannerError::InvalidBuild { .. })
+        ));
+        assert!(matches!(
+            parse_build("siren#123"),
+            Err(PlannerError::ParsingError { .. })
+        ));
+    }
+
+    #[test]
+    fn apply_build_test() {
+        let mut save_game = load_test_save();
+        let build = parse_build("assassin#0000000000-5555000000-5500000000").unwrap();
+        apply_build(&mut save_game, &build).unwrap();
+        assert_eq!(save_game.GeneralSkillPoints(), 2);
+        assert_eq!(skills::skill_rank(&save_game, build.class.action_skill), 1);
+        assert_eq!(
+            format_build(&export_build(&save_game).unwrap()),
+            "assassin#0000000000-5555000000-5500000000"
+        );
+
+        let build = parse_build("assassin#0000000000-5555000000-5530000000").unwrap();
+        assert_eq!(build.points(), 33);
+        assert!(matches!(
+            apply_build(&mut save_game, &build),
+            Err(PlannerError::NotEnoughSkillPoints { .. })
+        ));
+    }
+
+    #[test]
+    fn apply_mechromancer_build_test() {
+        let mut save_game = load_test_save();
+        let build = parse_build(
+            "https://bl2skills.com/mechromancer.html#550000000000-500000000000-10000000000",
+        )
+        .unwrap();
+        assert_eq!(build.class.name, "Mechromancer");
+        assert_eq!(build.points(), 16);
+        assert!(matches!(
+            apply_build(&mut save_game, &build),
+            Err(PlannerError::InvalidBuild { .. })
+        ));
+
+        save_game.set_PlayerClass(build.class.class.to_string());
+        save_game.set_IsDLCPlayerClass(true);
+        apply_build(&mut save_game, &build).unwrap();
+        assert_eq!(save_game.GeneralSkillPoints(), 16);
+        assert_eq!(
+            skills::skill_rank(
+                &save_game,
+                "GD_Tulip_Mechromancer_Skills.OrderedChaos.Anarchy"
+            ),
+            1
+        );
+        assert_eq!(
+            format_build(&export_build(&save_game).unwrap()),
+            "mechromancer#550000000000-500000000000-10000000000"
+        );
+
+        assert!(matches!(
+            parse_build("mechromancer#550000000000-500000000000-10001000000"),
+            Err(PlannerError::InvalidBuild { .. })
+        ));
+    }
+}
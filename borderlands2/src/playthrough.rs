@@ -0,0 +1,250 @@
+use crate::MissionPlaythroughData::MissionPlaythroughData;
+use crate::RegionGameStageData::RegionGameStageData;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+/// First story mission of every playthrough.
+pub const FIRST_MISSION: &str = "GD_Episode01.M_Ep1_Champion";
+/// Region every playthrough starts in.
+pub const START_REGION: &str = "GD_GameStages.Zone1.StartOfGame";
+/// Level needed to unlock Ultimate Vault Hunter Mode.
+pub const UVHM_LEVEL: i32 = 50;
+
+#[derive(Debug)]
+pub enum PlaythroughError {
+    Locked { msg: String },
+    RequirementNotMet { msg: String },
+}
+
+impl std::fmt::Display for PlaythroughError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PlaythroughError::Locked { msg } => write!(f, "Locked: {}", msg),
+            PlaythroughError::RequirementNotMet { msg } => {
+                write!(f, "RequirementNotMet: {}", msg)
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Playthrough {
+    Normal = 0,
+    TrueVaultHunter = 1,
+    UltimateVaultHunter = 2,
+}
+
+impl Playthrough {
+    pub const ALL: [Playthrough; 3] = [
+        Playthrough::Normal,
+        Playthrough::TrueVaultHunter,
+        Playthrough::UltimateVaultHunter,
+    ];
+
+    ///
+    /// Returns the index used by `MissionPlaythroughs` and `PlaythroughIndex`.
+    ///
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    ///
+    /// Returns the game stage of the start region when the playthrough begins.
+    ///
+    pub fn starting_game_stage(self) -> i32 {
+        match self {
+            Playthrough::Normal => 1,
+            Playthrough::TrueVaultHunter => 31,
+            Playthrough::UltimateVaultHunter => UVHM_LEVEL,
+        }
+    }
+}
+
+///
+/// Returns whether a playthrough can be played, `PlaythroughsCompleted` unlocks the next one.
+///
+pub fn is_unlocked(save: &WillowTwoPlayerSaveGame, playthrough: Playthrough) -> bool {
+    playthrough.index() as i32 <= save.PlaythroughsCompleted()
+}
+
+///
+/// Returns the playthrough the character was last played in.
+///
+pub fn current_playthrough(save: &WillowTwoPlayerSaveGame) -> Playthrough {
+    Playthrough::ALL
+        .iter()
+        .rev()
+        .copied()
+        .find(|playthrough| playthrough.index() as i32 <= save.LastPlaythroughNumber())
+        .unwrap_or(Playthrough::Normal)
+}
+
+fn fresh_playthrough(save: &mut WillowTwoPlayerSaveGame, playthrough: Playthrough) {
+    let index = playthrough.index();
+    while save.MissionPlaythroughs.len() <= index {
+        let mut data = MissionPlaythroughData::new();
+        data.set_ActiveMission(String::new());
+        save.MissionPlaythroughs.push(data);
+    }
+    let data = &mut save.MissionPlaythroughs[index];
+    data.set_ActiveMission(FIRST_MISSION.to_string());
+    data.MissionData.clear();
+
+    save.RegionGameStages
+        .retain(|stage| stage.PlaythroughIndex() != index as i32);
+    let mut stage = RegionGameStageData::new();
+    stage.set_Region(START_REGION.to_string());
+    stage.set_GameStage(playthrough.starting_game_stage());
+    stage.set_IsFromDLC(false);
+    stage.set_DLCPackageId(0);
+    stage.set_PlaythroughIndex(index as i32);
+    save.RegionGameStages.push(stage);
+}
+
+///
+/// Unlocks a playthrough and the ones before it.
+///
+/// Playthroughs without mission data get a fresh start and the character continues in the
+/// unlocked playthrough, the game shows the new playthrough notification on the next load.
+/// `PlotMissionNumber` is reset when the unlocked playthrough starts fresh. Ultimate Vault
+/// Hunter Mode needs level `UVHM_LEVEL`.
+///
+pub fn unlock_playthrough(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<(), PlaythroughError> {
+    if is_unlocked(save, playthrough) {
+        return Ok(());
+    }
+    if playthrough == Playthrough::UltimateVaultHunter && save.ExpLevel() < UVHM_LEVEL {
+        return Err(PlaythroughError::RequirementNotMet {
+            msg: format!(
+                "Ultimate Vault Hunter Mode needs level {}, not {}!",
+                UVHM_LEVEL,
+                save.ExpLevel()
+            ),
+        });
+    }
+    for unlocked in Playthrough::ALL
+        .iter()
+        .copied()
+        .filter(|unlocked| *unlocked <= playthrough)
+    {
+        let started = save
+            .MissionPlaythroughs
+            .get(unlocked.index())
+            .is_some_and(|data| !data.MissionData.is_empty());
+        if !started {
+            fresh_playthrough(save, unlocked);
+            if unlocked == playthrough {
+                save.set_PlotMissionNumber(0);
+            }
+        }
+    }
+    save.set_PlaythroughsCompleted(playthrough.index() as i32);
+    save.set_LastPlaythroughNumber(playthrough.index() as i32);
+    save.set_ShowNewPlaythroughNotification(true);
+    Ok(())
+}
+
+///
+/// Switches the playthrough the character continues in.
+///
+pub fn set_current_playthrough(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<(), PlaythroughError> {
+    if !is_unlocked(save, playthrough) {
+        return Err(PlaythroughError::Locked {
+            msg: format!("{:?} is not unlocked!", playthrough),
+        });
+    }
+    save.set_LastPlaythroughNumber(playthrough.index() as i32);
+    Ok(())
+}
+
+///
+/// Resets the missions and region game stages of an unlocked playthrough to its beginning.
+///
+/// `PlotMissionNumber` belongs to the current playthrough and is reset with it, resetting
+/// another playthrough leaves it as it is.
+///
+pub fn reset_playthrough(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<(), PlaythroughError> {
+    if !is_unlocked(save, playthrough) {
+        return Err(PlaythroughError::Locked {
+            msg: format!("{:?} is not unlocked!", playthrough),
+        });
+    }
+    fresh_playthrough(save, playthrough);
+    if current_playthrough(save) == playthrough {
+        save.set_PlotMissionNumber(0);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        current_playthrough, reset_playthrough, set_current_playthrough, unlock_playthrough,
+        Playthrough, FIRST_MISSION,
+    };
+    use crate::load_test_save;
+
+    #[test]
+    fn unlock_playthrough_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(
+            current_playthrough(&save_game),
+            Playthrough::TrueVaultHunter
+        );
+        assert!(set_current_playthrough(&mut save_game, Playthrough::UltimateVaultHunter).is_err());
+        assert!(unlock_playthrough(&mut save_game, Playthrough::UltimateVaultHunter).is_err());
+
+        save_game.set_ExpLevel(50);
+        unlock_playthrough(&mut save_game, Playthrough::UltimateVaultHunter).unwrap();
+        assert_eq!(save_game.PlaythroughsCompleted(), 2);
+        assert_eq!(save_game.LastPlaythroughNumber(), 2);
+        assert!(save_game.ShowNewPlaythroughNotification());
+        assert_eq!(save_game.MissionPlaythroughs[1].MissionData.len(), 29);
+        assert_eq!(save_game.PlotMissionNumber(), 0);
+        assert_eq!(
+            save_game.MissionPlaythroughs[2].ActiveMission(),
+            FIRST_MISSION
+        );
+        assert_eq!(
+            save_game
+                .RegionGameStages
+                .iter()
+                .filter(|stage| stage.PlaythroughIndex() == 2)
+                .count(),
+            1
+        );
+        set_current_playthrough(&mut save_game, Playthrough::Normal).unwrap();
+        assert_eq!(current_playthrough(&save_game), Playthrough::Normal);
+    }
+
+    #[test]
+    fn reset_playthrough_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(save_game.PlotMissionNumber(), 7);
+        reset_playthrough(&mut save_game, Playthrough::Normal).unwrap();
+        assert!(save_game.MissionPlaythroughs[0].MissionData.is_empty());
+        assert_eq!(save_game.PlotMissionNumber(), 7);
+
+        let mut save_game = load_test_save();
+        reset_playthrough(&mut save_game, Playthrough::TrueVaultHunter).unwrap();
+        assert_eq!(save_game.PlotMissionNumber(), 0);
+        assert!(save_game.MissionPlaythroughs[1].MissionData.is_empty());
+        assert_eq!(save_game.MissionPlaythroughs[0].MissionData.len(), 99);
+        let stages: Vec<_> = save_game
+            .RegionGameStages
+            .iter()
+            .filter(|stage| stage.PlaythroughIndex() == 1)
+            .collect();
+        assert_eq!(stages.len(), 1);
+        assert_eq!(stages[0].GameStage(), 31);
+        assert!(reset_playthrough(&mut save_game, Playthrough::UltimateVaultHunter).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::experience::{MAX_LEVEL, MIN_LEVEL};
+use crate::playthrough::Playthrough;
+use crate::RegionGameStageData::RegionGameStageData;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+#[derive(Debug)]
+pub enum RegionError {
+    InvalidGameStage { msg: String },
+}
+
+impl std::fmt::Display for RegionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RegionError::InvalidGameStage { msg } => write!(f, "InvalidGameStage: {}", msg),
+        }
+    }
+}
+
+///
+/// Level the regions of a playthrough are rescaled to.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StageTarget {
+    /// A fixed game stage.
+    Fixed(i32),
+    /// The level of the character.
+    Character,
+}
+
+impl StageTarget {
+    ///
+    /// Returns the game stage the target resolves to for a character.
+    ///
+    pub fn game_stage(self, save: &WillowTwoPlayerSaveGame) -> i32 {
+        match self {
+            StageTarget::Fixed(stage) => stage,
+            StageTarget::Character => save.ExpLevel(),
+        }
+    }
+}
+
+fn check_game_stage(stage: i32) -> Result<(), RegionError> {
+    if !(MIN_LEVEL..=MAX_LEVEL).contains(&stage) {
+        return Err(RegionError::InvalidGameStage {
+            msg: format!(
+                "Game stage {} is outside of {} to {}!",
+                stage, MIN_LEVEL, MAX_LEVEL
+            ),
+        });
+    }
+    Ok(())
+}
+
+///
+/// Returns the regions entered in a playthrough with their game stage.
+///
+pub fn game_stages(save: &WillowTwoPlayerSaveGame, playthrough: Playthrough) -> Vec<(&str, i32)> {
+    save.RegionGameStages
+        .iter()
+        .filter(|data| data.PlaythroughIndex() == playthrough.index() as i32)
+        .map(|data| (data.Region(), data.GameStage()))
+        .collect()
+}
+
+///
+/// Returns the game stage of a region in a playthrough if it was entered.
+///
+pub fn game_stage(
+    save: &WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    region: &str,
+) -> Option<i32> {
+    game_stages(save, playthrough)
+        .into_iter()
+        .find(|(name, _)| *name == region)
+        .map(|(_, stage)| stage)
+}
+
+///
+/// Sets the game stage of a region in a playthrough, regions not entered yet are added.
+///
+pub fn set_game_stage(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    region: &str,
+    stage: i32,
+) -> Result<(), RegionError> {
+    check_game_stage(stage)?;
+    let index = playthrough.index() as i32;
+    match save
+        .RegionGameStages
+        .iter_mut()
+        .find(|data| data.Region() == region && data.PlaythroughIndex() == index)
+    {
+        Some(data) => data.set_GameStage(stage),
+        None => {
+            let mut data = RegionGameStageData::new();
+            data.set_Region(region.to_string());
+            data.set_GameStage(stage);
+            data.set_IsFromDLC(false);
+            data.set_DLCPackageId(0);
+            data.set_PlaythroughIndex(index);
+            save.RegionGameStages.push(data);
+        }
+    }
+    Ok(())
+}
+
+///
+/// Rescales every region entered in a playthrough to the same game stage.
+///
+pub fn rescale_game_stages(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    target: StageTarget,
+) -> Result<(), RegionError> {
+    let stage = target.game_stage(save);
+    check_game_stage(stage)?;
+    save.RegionGameStages
+        .iter_mut()
+        .filter(|data| data.PlaythroughIndex() == playthrough.index() as i32)
+        .for_each(|data| data.set_GameStage(stage));
+    Ok(())
+}
+
+///
+/// Aligns the region game stages of a playthrough between two co-op partners.
+///
+/// Both saves end up with every region either of them entered, at the higher of the two
+/// game stages.
+///
+pub fn sync_game_stages(
+    save: &mut WillowTwoPlayerSaveGame,
+    other: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+) -> Result<(), RegionError> {
+    let mut stages: Vec<(String, i32)> = Vec::new();
+    for (region, stage) in game_stages(save, playthrough)
+        .into_iter()
+        .chain(game_stages(other, playthrough))
+    {
+        match stages.iter_mut().find(|(name, _)| name == region) {
+            Some((_, highest)) => *highest = (*highest).max(stage),
+            None => stages.push((region.to_string(), stage)),
+        }
+    }
+
+    let mut working = save.clone();
+    let mut other_working = other.clone();
+    for (region, stage) in &stages {
+        set_game_stage(&mut working, playthrough, region, *stage)?;
+        set_game_stage(&mut other_working, playthrough, region, *stage)?;
+    }
+    *save = working;
+    *other = other_working;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        game_stage, game_stages, rescale_game_stages, set_game_stage, sync_game_stages, StageTarget,
+    };
+    use crate::load_test_save;
+    use crate::playthrough::Playthrough;
+
+    #[test]
+    fn rescale_game_stages_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(game_stages(&save_game, Playthrough::Normal).len(), 40);
+        assert_eq!(
+            game_stage(
+                &save_game,
+                Playthrough::TrueVaultHunter,
+                "GD_GameStages.Zone1.Ice_A"
+            ),
+            Some(33)
+        );
+
+        rescale_game_stages(&mut save_game, Playthrough::Normal, StageTarget::Character).unwrap();
+        assert!(game_stages(&save_game, Playthrough::Normal)
+            .iter()
+            .all(|(_, stage)| *stage == 37));
+        assert_eq!(
+            game_stage(
+                &save_game,
+                Playthrough::TrueVaultHunter,
+                "GD_GameStages.Zone1.Ice_A"
+            ),
+            Some(33)
+        );
+        assert!(
+            rescale_game_stages(&mut save_game, Playthrough::Normal, StageTarget::Fixed(91))
+                .is_err()
+        );
+        assert!(set_game_stage(
+            &mut save_game,
+            Playthrough::Normal,
+            "GD_GameStages.Zone1.Dam",
+            0
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn sync_game_stages_test() {
+        let mut save_game = load_test_save();
+        let mut other = load_test_save();
+        other
+            .RegionGameStages
+            .retain(|data| data.PlaythroughIndex() != 1);
+        set_game_stage(
+            &mut other,
+            Playthrough::TrueVaultHunter,
+            "GD_GameStages.Zone1.Ice_A",
+            40,
+        )
+        .unwrap();
+
+        sync_game_stages(&mut save_game, &mut other, Playthrough::TrueVaultHunter).unwrap();
+        let mut stages = game_stages(&save_game, Playthrough::TrueVaultHunter);
+        let mut other_stages = game_stages(&other, Playthrough::TrueVaultHunter);
+        stages.sort();
+        other_stages.sort();
+        assert_eq!(stages, other_stages);
+        assert_eq!(game_stages(&other, Playthrough::TrueVaultHunter).len(), 13);
+        assert_eq!(
+            game_stage(
+                &save_game,
+                Playthrough::TrueVaultHunter,
+                "GD_GameStages.Zone1.Ice_A"
+            ),
+            Some(40)
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use crate::inventory::{self, InventoryError, Overflow, Placement};
+use crate::PackedItemData::PackedItemData;
+use crate::PackedItemDataOptional::PackedItemDataOptional;
+use crate::PackedWeaponData::PackedWeaponData;
+use crate::PackedWeaponDataOptional::PackedWeaponDataOptional;
+use crate::PendingMissionRewards::PendingMissionRewards;
+use crate::PlayerMark::PlayerMark;
+use crate::QuickWeaponSlot::QuickWeaponSlot;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+use protobuf::Enum;
+
+#[derive(Debug)]
+pub enum RewardError {
+    NoSuchMission { msg: String },
+    MissingSerial { msg: String },
+    InventoryError { msg: String },
+}
+
+impl std::fmt::Display for RewardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RewardError::NoSuchMission { msg } => write!(f, "NoSuchMission: {}", msg),
+            RewardError::MissingSerial { msg } => write!(f, "MissingSerial: {}", msg),
+            RewardError::InventoryError { msg } => write!(f, "InventoryError: {}", msg),
+        }
+    }
+}
+
+impl From<InventoryError> for RewardError {
+    fn from(err: InventoryError) -> Self {
+        RewardError::InventoryError {
+            msg: err.to_string(),
+        }
+    }
+}
+
+///
+/// Returns the unclaimed rewards of all missions.
+///
+pub fn pending_rewards(save: &WillowTwoPlayerSaveGame) -> &[PendingMissionRewards] {
+    &save.PendingMissionRewards
+}
+
+///
+/// Returns the number of weapons and items waiting in a reward.
+///
+pub fn reward_count(rewards: &PendingMissionRewards) -> usize {
+    rewards.WeaponRewards.len()
+        + rewards.ItemRewards.len()
+        + rewards.PackedWeaponRewards.len()
+        + rewards.PackedItemRewards.len()
+}
+
+fn packed_weapon(
+    mission: &str,
+    reward: &PackedWeaponDataOptional,
+) -> Result<PackedWeaponData, RewardError> {
+    if !reward.has_InventorySerialNumber() {
+        return Err(RewardError::MissingSerial {
+            msg: format!("A weapon reward of {} has no serial!", mission),
+        });
+    }
+    let mut weapon = PackedWeaponData::new();
+    weapon.set_InventorySerialNumber(reward.InventorySerialNumber().to_vec());
+    weapon.set_QuickSlot(QuickWeaponSlot::None);
+    weapon.set_Mark(if reward.has_Mark() {
+        reward.Mark()
+    } else {
+        PlayerMark::Standard
+    });
+    if reward.has_Unknown4() {
+        weapon.set_Unknown4(reward.Unknown4());
+    }
+    Ok(weapon)
+}
+
+fn packed_item(
+    mission: &str,
+    reward: &PackedItemDataOptional,
+) -> Result<PackedItemData, RewardError> {
+    if !reward.has_InventorySerialNumber() {
+        return Err(RewardError::MissingSerial {
+            msg: format!("An item reward of {} has no serial!", mission),
+        });
+    }
+    let mut item = PackedItemData::new();
+    item.set_InventorySerialNumber(reward.InventorySerialNumber().to_vec());
+    item.set_Quantity(if reward.has_Quantity() {
+        reward.Quantity()
+    } else {
+        1
+    });
+    item.set_Equipped(false);
+    item.set_Mark(if reward.has_Mark() {
+        reward.Mark().value()
+    } else {
+        PlayerMark::Standard.value()
+    });
+    Ok(item)
+}
+
+fn grant(
+    save: &mut WillowTwoPlayerSaveGame,
+    rewards: &PendingMissionRewards,
+    overflow: Overflow,
+    placements: &mut Vec<Placement>,
+) -> Result<(), RewardError> {
+    let mission = rewards.Mission();
+    for weapon in &rewards.WeaponRewards {
+        let mut weapon = weapon.clone();
+        weapon.set_QuickSlot(QuickWeaponSlot::None);
+        placements.push(inventory::add_weapon(save, weapon, overflow)?);
+    }
+    for item in &rewards.ItemRewards {
+        let mut item = item.clone();
+        item.set_Equipped(false);
+        placements.push(inventory::add_item(save, item, overflow)?);
+    }
+    for weapon in &rewards.PackedWeaponRewards {
+        let weapon = packed_weapon(mission, weapon)?;
+        placements.push(inventory::add_packed_weapon(save, weapon, overflow)?);
+    }
+    for item in &rewards.PackedItemRewards {
+        let item = packed_item(mission, item)?;
+        placements.push(inventory::add_packed_item(save, item, overflow)?);
+    }
+    Ok(())
+}
+
+///
+/// Moves the pending rewards of a mission into the inventory and returns where they went.
+///
+/// The save is only changed if all rewards fit, the granted rewards are removed from
+/// `PendingMissionRewards`.
+///
+pub fn grant_rewards(
+    save: &mut WillowTwoPlayerSaveGame,
+    mission: &str,
+    overflow: Overflow,
+) -> Result<Vec<Placement>, RewardError> {
+    if !save
+        .PendingMissionRewards
+        .iter()
+        .any(|rewards| rewards.Mission() == mission)
+    {
+        return Err(RewardError::NoSuchMission {
+            msg: format!("{} has no pending rewards!", mission),
+        });
+    }
+
+    let mut working = save.clone();
+    let mut placements = Vec::new();
+    let (granted, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut working.PendingMissionRewards)
+        .into_iter()
+        .partition(|rewards| rewards.Mission() == mission);
+    working.PendingMissionRewards = pending;
+    for rewards in &granted {
+        grant(&mut working, rewards, overflow, &mut placements)?;
+    }
+    *save = working;
+    Ok(placements)
+}
+
+///
+/// Moves the pending rewards of all missions into the inventory, see `grant_rewards`.
+///
+pub fn grant_all_rewards(
+    save: &mut WillowTwoPlayerSaveGame,
+    overflow: Overflow,
+) -> Result<Vec<Placement>, RewardError> {
+    let mut working = save.clone();
+    let mut placements = Vec::new();
+    for rewards in std::mem::take(&mut working.PendingMissionRewards) {
+        grant(&mut working, &rewards, overflow, &mut placements)?;
+    }
+    *save = working;
+    Ok(placements)
+}
+
+///
+/// Drops the pending rewards of a mission, or of all missions if `mission` is `None`.
+///
+/// Returns the number of dropped weapons and items.
+///
+pub fn clear_rewards(save: &mut WillowTwoPlayerSaveGame, mission: Option<&str>) -> usize {
+    let mut cleared = 0;
+    save.PendingMissionRewards.retain(|rewards| {
+        let matches = match mission {
+            Some(mission) => rewards.Mission() == mission,
+            None => true,
+        };
+        if matches {
+            cleared += reward_count(rewards);
+        }
+        !matches
+    });
+    cleared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clear_rewards, grant_rewards, pending_rewards, reward_count};
+    use crate::builder::GearBuilder;
+    use crate::equipment::GearIndex;
+    use crate::inventory::{Overflow, Placement};
+    use crate::load_test_save;
+    use crate::PackedWeaponDataOptional::PackedWeaponDataOptional;
+    use crate::PendingMissionRewards::PendingMissionRewards;
+
+    fn test_rewards(
+        save_game: &crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame,
+        mission: &str,
+    ) -> PendingMissionRewards {
+        let mut rewards = PendingMissionRewards::new();
+        rewards.set_Mission(mission.to_string());
+        rewards.set_IsFromDLC(false);
+        rewards.set_DLCPackageId(0);
+        rewards.ItemRewards.push(
+            GearBuilder::new("GD_ItemGrades.Shields.ItemGrade_Gear_Shield_Standard_03_Rare")
+                .unwrap()
+                .build_item()
+                .unwrap(),
+        );
+        let mut weapon = PackedWeaponDataOptional::new();
+        weapon.set_InventorySerialNumber(
+            save_game.PackedWeaponData[2]
+                .InventorySerialNumber()
+                .to_vec(),
+        );
+        rewards.PackedWeaponRewards.push(weapon);
+        rewards
+    }
+
+    #[test]
+    fn grant_rewards_test() {
+        let mut save_game = load_test_save();
+        assert!(pending_rewards(&save_game).is_empty());
+        let rewards = test_rewards(&save_game, "GD_Episode01.M_Ep1_Champion");
+        save_game.PendingMissionRewards.push(rewards);
+
+        let buffer = crate::write_save_mem(&save_game).unwrap();
+        let mut save_game = crate::load_save_mem(buffer).unwrap();
+        assert_eq!(reward_count(&pending_rewards(&save_game)[0]), 2);
+
+        let weapons = save_game.PackedWeaponData.len();
+        let placements = grant_rewards(
+            &mut save_game,
+            "GD_Episode01.M_Ep1_Champion",
+            Overflow::Refuse,
+        )
+        .unwrap();
+        assert_eq!(
+            placements,
+            vec![
+                Placement::Backpack(GearIndex::Item(0)),
+                Placement::Backpack(GearIndex::PackedWeapon(weapons)),
+            ]
+        );
+        assert!(pending_rewards(&save_game).is_empty());
+        assert!(grant_rewards(
+            &mut save_game,
+            "GD_Episode01.M_Ep1_Champion",
+            Overflow::Refuse
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn clear_rewards_test() {
+        let mut save_game = load_test_save();
+        let first = test_rewards(&save_game, "GD_Episode01.M_Ep1_Champion");
+        let second = test_rewards(&save_game, "GD_Episode02.M_Ep2b_Henchman");
+        save_game.PendingMissionRewards.push(first);
+        save_game.PendingMissionRewards.push(second);
+
+        save_game
+            .InventorySlotData
+            .mut_or_insert_default()
+            .set_InventorySlotMax(0);
+        assert!(grant_rewards(
+            &mut save_game,
+            "GD_Episode02.M_Ep2b_Henchman",
+            Overflow::Refuse
+        )
+        .is_err());
+        assert_eq!(pending_rewards(&save_game).len(), 2);
+
+        assert_eq!(
+            clear_rewards(&mut save_game, Some("GD_Episode02.M_Ep2b_Henchman")),
+            2
+        );
+        assert_eq!(clear_rewards(&mut save_game, None), 2);
+        assert!(pending_rewards(&save_game).is_empty());
+    }
+}
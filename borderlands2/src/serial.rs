@@ -0,0 +1,460 @@
+use crate::assets::{AssetKind, AssetReference};
+
+///
+/// Serial format version written by the game.
+///
+pub const SERIAL_VERSION: u8 = 7;
+
+///
+/// Minimum length of the packed data used when calculating the checksum.
+///
+const CHECKSUM_PADDED_LENGTH: usize = 33;
+
+#[derive(Debug)]
+pub enum SerialError {
+    LengthError { msg: String },
+    ChecksumError { msg: String },
+    EncodingError { msg: String },
+}
+
+impl std::fmt::Display for SerialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SerialError::LengthError { msg } => write!(f, "LengthError: {}", msg),
+            SerialError::ChecksumError { msg } => write!(f, "ChecksumError: {}", msg),
+            SerialError::EncodingError { msg } => write!(f, "EncodingError: {}", msg),
+        }
+    }
+}
+
+///
+/// Decoded content of an `InventorySerialNumber`.
+///
+/// The asset references are still raw indices, use the functions in `assets` to resolve them.
+/// Parts that are not set are `None`.
+///
+/// Decoding and encoding a serial gives back the same bytes, so `parts` holds exactly the parts
+/// stored in the serial and `trailing` any bits after them that are not the usual padding.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InventorySerial {
+    pub is_weapon: bool,
+    pub version: u8,
+    pub seed: i32,
+    pub set_id: u8,
+    pub item_type: AssetReference,
+    pub balance: AssetReference,
+    pub manufacturer: Option<AssetReference>,
+    pub manufacturer_grade_index: u8,
+    pub game_stage: u8,
+    pub parts: Vec<Option<AssetReference>>,
+    pub trailing: Vec<bool>,
+}
+
+///
+/// Returns the asset kinds used for the type, the balance and the parts of a serial.
+///
+pub(crate) fn asset_kinds(is_weapon: bool) -> (AssetKind, AssetKind, AssetKind) {
+    if is_weapon {
+        (
+            AssetKind::WeaponType,
+            AssetKind::WeaponBalance,
+            AssetKind::WeaponPart,
+        )
+    } else {
+        (
+            AssetKind::ItemType,
+            AssetKind::ItemBalance,
+            AssetKind::ItemPart,
+        )
+    }
+}
+
+///
+/// Calculates the CRC32 (IEEE) checksum of the data.
+///
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xffff_ffff;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+///
+/// Applies the serial stream cipher. The operation is its own inverse.
+///
+fn xor_data(data: &mut [u8], key: i32) {
+    let mut key = key as u32 as u64;
+    for byte in data.iter_mut() {
+        key = (key * 279_470_273) % 4_294_967_291;
+        *byte ^= (key & 0xff) as u8;
+    }
+}
+
+///
+/// Calculates the folded checksum over the header and the packed data.
+///
+fn checksum(header: &[u8], data: &[u8]) -> u16 {
+    let mut buffer = Vec::with_capacity(header.len() + 2 + CHECKSUM_PADDED_LENGTH);
+    buffer.extend_from_slice(header);
+    buffer.extend_from_slice(&[0xff, 0xff]);
+    buffer.extend_from_slice(data);
+    while buffer.len() < header.len() + 2 + CHECKSUM_PADDED_LENGTH {
+        buffer.push(0xff);
+    }
+    let hash = crc32(&buffer);
+    ((hash >> 16) ^ hash) as u16
+}
+
+///
+/// Reads values of a fixed bit width from a byte buffer, least significant bit first.
+///
+struct BitReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn read(&mut self, bits: u32) -> Option<u32> {
+        if self.offset + bits as usize > self.data.len() * 8 {
+            return None;
+        }
+        let mut value = 0;
+        for i in 0..bits as usize {
+            let position = self.offset + i;
+            if (self.data[position / 8] >> (position % 8)) & 1 == 1 {
+                value |= 1 << i;
+            }
+        }
+        self.offset += bits as usize;
+        Some(value)
+    }
+
+    ///
+    /// Returns the bits left after the last read value.
+    ///
+    fn remaining(&self) -> Vec<bool> {
+        (self.offset..self.data.len() * 8)
+            .map(|position| (self.data[position / 8] >> (position % 8)) & 1 == 1)
+            .collect()
+    }
+}
+
+///
+/// Writes values of a fixed bit width to a byte buffer, least significant bit first.
+///
+#[derive(Default)]
+struct BitWriter {
+    data: Vec<u8>,
+    offset: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        for i in 0..bits as usize {
+            let position = self.offset + i;
+            if position / 8 >= self.data.len() {
+                self.data.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                self.data[position / 8] |= 1 << (position % 8);
+            }
+        }
+        self.offset += bits as usize;
+    }
+
+    ///
+    /// Returns the written bytes with the unused bits of the last byte set.
+    ///
+    fn finish(mut self) -> Vec<u8> {
+        let used_bits = self.offset % 8;
+        if used_bits > 0 {
+            let last = self.data.len() - 1;
+            self.data[last] |= 0xff << used_bits;
+        }
+        self.data
+    }
+}
+
+fn bit_mask(bits: u32) -> u32 {
+    (1 << bits) - 1
+}
+
+fn read_reference(reader: &mut BitReader, kind: AssetKind) -> Option<Option<AssetReference>> {
+    let (sublibrary_bits, asset_bits) = kind.bit_sizes();
+    let value = reader.read(sublibrary_bits + asset_bits)?;
+    if value == bit_mask(sublibrary_bits + asset_bits) {
+        return Some(None);
+    }
+    let sublibrary = value >> asset_bits;
+    Some(Some(AssetReference {
+        use_set: (sublibrary >> (sublibrary_bits - 1)) & 1 == 1,
+        sublibrary: sublibrary & bit_mask(sublibrary_bits - 1),
+        asset: value & bit_mask(asset_bits),
+    }))
+}
+
+fn write_reference(writer: &mut BitWriter, kind: AssetKind, reference: &Option<AssetReference>) {
+    let (sublibrary_bits, asset_bits) = kind.bit_sizes();
+    let value = match reference {
+        Some(reference) => {
+            let sublibrary = ((reference.use_set as u32) << (sublibrary_bits - 1))
+                | (reference.sublibrary & bit_mask(sublibrary_bits - 1));
+            (sublibrary << asset_bits) | (reference.asset & bit_mask(asset_bits))
+        }
+        None => bit_mask(sublibrary_bits + asset_bits),
+    };
+    writer.write(value, sublibrary_bits + asset_bits);
+}
+
+///
+/// Decodes an `InventorySerialNumber` as stored in `PackedWeaponData`, `PackedItemData`
+/// and `BankSlot`.
+///
+pub fn decode_serial(serial: &[u8]) -> Result<InventorySerial, SerialError> {
+    if serial.len() < 7 {
+        return Err(SerialError::LengthError {
+            msg: format!("Serial of {} bytes is to small!", serial.len()),
+        });
+    }
+    let header = &serial[..5];
+    let seed = i32::from_be_bytes([serial[1], serial[2], serial[3], serial[4]]);
+
+    let mut body = serial[5..].to_vec();
+    xor_data(&mut body, seed >> 5);
+    let steps = (seed & 31) as usize % body.len();
+    body.rotate_right(steps);
+
+    let stored_checksum = u16::from_be_bytes([body[0], body[1]]);
+    let data = &body[2..];
+    if checksum(header, data) != stored_checksum {
+        return Err(SerialError::ChecksumError {
+            msg: "Serial checksum does not match!".to_string(),
+        });
+    }
+
+    let is_weapon = serial[0] & 0x80 != 0;
+    let (type_kind, balance_kind, part_kind) = asset_kinds(is_weapon);
+
+    let mut reader = BitReader { data, offset: 0 };
+    let truncated = || SerialError::LengthError {
+        msg: "Serial ends inside the header!".to_string(),
+    };
+    let set_id = reader.read(8).ok_or_else(truncated)? as u8;
+    let item_type = read_reference(&mut reader, type_kind).ok_or_else(truncated)?;
+    let balance = read_reference(&mut reader, balance_kind).ok_or_else(truncated)?;
+    let manufacturer =
+        read_reference(&mut reader, AssetKind::Manufacturer).ok_or_else(truncated)?;
+    let manufacturer_grade_index = reader.read(7).ok_or_else(truncated)? as u8;
+    let game_stage = reader.read(7).ok_or_else(truncated)? as u8;
+
+    let (item_type, balance) = match (item_type, balance) {
+        (Some(item_type), Some(balance)) => (item_type, balance),
+        _ => {
+            return Err(SerialError::EncodingError {
+                msg: "Serial has no type or balance!".to_string(),
+            })
+        }
+    };
+
+    let mut parts = Vec::new();
+    while parts.len() < 11 {
+        match read_reference(&mut reader, part_kind) {
+            Some(part) => parts.push(part),
+            None => break,
+        }
+    }
+    // Fewer than eight set bits are the padding written by `BitWriter::finish`.
+    let mut trailing = reader.remaining();
+    if trailing.len() < 8 && trailing.iter().all(|bit| *bit) {
+        trailing.clear();
+    }
+
+    Ok(InventorySerial {
+        is_weapon,
+        version: serial[0] & 0x7f,
+        seed,
+        set_id,
+        item_type,
+        balance,
+        manufacturer,
+        manufacturer_grade_index,
+        game_stage,
+        parts,
+        trailing,
+    })
+}
+
+///
+/// Encodes a serial into the `InventorySerialNumber` form.
+///
+/// Every entry of `parts` is written, the game leaves out trailing parts that are not set.
+///
+pub fn encode_serial(serial: &InventorySerial) -> Vec<u8> {
+    let (type_kind, balance_kind, part_kind) = asset_kinds(serial.is_weapon);
+
+    let mut writer = BitWriter::default();
+    writer.write(serial.set_id as u32, 8);
+    write_reference(&mut writer, type_kind, &Some(serial.item_type));
+    write_reference(&mut writer, balance_kind, &Some(serial.balance));
+    write_reference(&mut writer, AssetKind::Manufacturer, &serial.manufacturer);
+    writer.write(serial.manufacturer_grade_index as u32, 7);
+    writer.write(serial.game_stage as u32, 7);
+
+    for part in &serial.parts {
+        write_reference(&mut writer, part_kind, part);
+    }
+    for bit in &serial.trailing {
+        writer.write(*bit as u32, 1);
+    }
+    let data = writer.finish();
+
+    let mut header = vec![((serial.is_weapon as u8) << 7) | (serial.version & 0x7f)];
+    header.extend_from_slice(&serial.seed.to_be_bytes());
+
+    let mut body = checksum(&header, &data).to_be_bytes().to_vec();
+    body.extend_from_slice(&data);
+    let steps = (serial.seed & 31) as usize % body.len();
+    body.rotate_left(steps);
+    xor_data(&mut body, serial.seed >> 5);
+
+    header.extend_from_slice(&body);
+    header
+}
+
+///
+/// Returns the seed that follows `seed`, used to give every serial of a batch its own seed.
+///
+pub fn next_seed(seed: i32) -> i32 {
+    ((seed as u32 as u64 * 279_470_273 + 1) % 4_294_967_291) as u32 as i32
+}
+
+///
+/// Returns a seed derived from the current time, for serials that need a fresh seed.
+///
+pub fn time_seed() -> i32 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    next_seed(now.as_nanos() as i32)
+}
+
+///
+/// Re-encrypts a serial with another seed, the content of the serial stays the same.
+///
+pub fn reseed_serial(serial: &[u8], seed: i32) -> Result<Vec<u8>, SerialError> {
+    let mut decoded = decode_serial(serial)?;
+    decoded.seed = seed;
+    Ok(encode_serial(&decoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_serial, encode_serial};
+    use crate::assets::AssetReference;
+    use crate::load_test_save;
+
+    #[test]
+    fn serial_round_trip_test() {
+        let save_game = load_test_save();
+
+        let serials = save_game
+            .PackedWeaponData
+            .iter()
+            .map(|weapon| weapon.InventorySerialNumber())
+            .chain(
+                save_game
+                    .PackedItemData
+                    .iter()
+                    .map(|item| item.InventorySerialNumber()),
+            )
+            .chain(
+                save_game
+                    .BankSlots
+                    .iter()
+                    .map(|slot| slot.InventorySerialNumber()),
+            );
+        let mut count = 0;
+        for serial in serials {
+            let decoded = decode_serial(serial).unwrap();
+            assert_eq!(decoded.version, 7);
+            assert_eq!(encode_serial(&decoded), serial);
+            count += 1;
+        }
+        assert_eq!(count, 49);
+    }
+
+    #[test]
+    fn real_gear_references_test() {
+        let save_game = load_test_save();
+        let reference = |sublibrary, asset| AssetReference {
+            use_set: false,
+            sublibrary,
+            asset,
+        };
+
+        let first = decode_serial(save_game.PackedWeaponData[0].InventorySerialNumber()).unwrap();
+        assert!(first.is_weapon);
+        assert_eq!(first.set_id, 0);
+        assert_eq!(first.item_type, reference(33, 0));
+        assert_eq!(first.balance, reference(130, 22));
+        assert_eq!(first.manufacturer, Some(reference(1, 1)));
+        assert_eq!(first.manufacturer_grade_index, 37);
+        assert_eq!(first.game_stage, 37);
+        assert_eq!(first.parts[0], Some(reference(11, 177)));
+
+        // Every weapon package has a sublibrary of types, of balances and of parts.
+        let packages = [
+            (26, 126, 3),
+            (29, 127, 5),
+            (30, 128, 6),
+            (32, 129, 10),
+            (33, 130, 11),
+            (34, 131, 12),
+        ];
+        let weapons = save_game
+            .PackedWeaponData
+            .iter()
+            .map(|weapon| weapon.InventorySerialNumber())
+            .chain(
+                save_game
+                    .BankSlots
+                    .iter()
+                    .map(|slot| slot.InventorySerialNumber()),
+            );
+        let mut dlc_sets = Vec::new();
+        for serial in weapons {
+            let decoded = decode_serial(serial).unwrap();
+            assert!(decoded.is_weapon);
+            let (_, balance, parts) = *packages
+                .iter()
+                .find(|(item_type, _, _)| *item_type == decoded.item_type.sublibrary)
+                .unwrap();
+            if decoded.balance.use_set {
+                dlc_sets.push(decoded.set_id);
+            } else {
+                assert_eq!(decoded.balance.sublibrary, balance);
+            }
+            for part in decoded.parts.iter().flatten() {
+                // Prefixes can also come from the shared sublibrary 9.
+                assert!(part.use_set || part.sublibrary == parts || part.sublibrary == 9);
+            }
+        }
+        assert_eq!(dlc_sets, vec![4, 4, 10]);
+    }
+
+    #[test]
+    fn serial_checksum_test() {
+        let mut serial = vec![0x87, 0x12, 0x34, 0x56, 0x78];
+        serial.extend_from_slice(&[0xab; 34]);
+        assert!(decode_serial(&serial).is_err());
+    }
+}
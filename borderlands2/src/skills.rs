@@ -0,0 +1,826 @@
+use crate::experience;
+use crate::SkillData::SkillData;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+/// Number of tiers of every skill tree.
+pub const TIERS: i32 = 6;
+/// Points that have to be spent in the lower tiers of a tree to unlock the next tier.
+pub const POINTS_PER_TIER: i32 = 5;
+
+#[derive(Debug)]
+pub enum SkillError {
+    UnknownClass { msg: String },
+    UnknownSkill { msg: String },
+    InvalidRank { msg: String },
+    TierLocked { msg: String },
+    NotEnoughSkillPoints { msg: String },
+}
+
+impl std::fmt::Display for SkillError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SkillError::UnknownClass { msg } => write!(f, "UnknownClass: {}", msg),
+            SkillError::UnknownSkill { msg } => write!(f, "UnknownSkill: {}", msg),
+            SkillError::InvalidRank { msg } => write!(f, "InvalidRank: {}", msg),
+            SkillError::TierLocked { msg } => write!(f, "TierLocked: {}", msg),
+            SkillError::NotEnoughSkillPoints { msg } => {
+                write!(f, "NotEnoughSkillPoints: {}", msg)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SkillDefinition {
+    /// Full path as stored in `SkillData.Skill`.
+    pub name: &'static str,
+    /// Tier starting at 1 for the top row of the tree.
+    pub tier: i32,
+    pub max_rank: i32,
+}
+
+#[derive(Debug)]
+pub struct SkillTree {
+    pub name: &'static str,
+    pub skills: &'static [SkillDefinition],
+}
+
+impl SkillTree {
+    ///
+    /// Returns the skill of the tree with the given full path.
+    ///
+    pub fn skill(&self, name: &str) -> Option<&'static SkillDefinition> {
+        self.skills.iter().find(|skill| skill.name == name)
+    }
+}
+
+///
+/// Skill trees and action skill of a player class.
+///
+#[derive(Debug)]
+pub struct ClassSkills {
+    /// Full path as stored in `PlayerClass`.
+    pub class: &'static str,
+    pub name: &'static str,
+    /// The action skill has a single rank, which costs one skill point.
+    pub action_skill: &'static str,
+    pub trees: &'static [SkillTree],
+}
+
+impl ClassSkills {
+    ///
+    /// Returns the tree and definition of a skill of the class.
+    ///
+    pub fn skill(&self, name: &str) -> Option<(&'static SkillTree, &'static SkillDefinition)> {
+        self.trees
+            .iter()
+            .find_map(|tree| tree.skill(name).map(|skill| (tree, skill)))
+    }
+}
+
+///
+/// Returns the skill trees of a player class.
+///
+pub fn class_skills(class: &str) -> Option<&'static ClassSkills> {
+    CLASSES.iter().find(|skills| skills.class == class)
+}
+
+///
+/// Returns the skill trees of all player classes.
+///
+pub fn classes() -> &'static [ClassSkills] {
+    CLASSES
+}
+
+///
+/// Returns the number of points that unlock a tier.
+///
+pub fn tier_threshold(tier: i32) -> i32 {
+    (tier - 1).max(0) * POINTS_PER_TIER
+}
+
+///
+/// Returns the rank of a skill, skills without `SkillData` have rank 0.
+///
+pub fn skill_rank(save: &WillowTwoPlayerSaveGame, skill: &str) -> i32 {
+    save.SkillData
+        .iter()
+        .find(|data| data.Skill() == skill)
+        .map(|data| data.Grade())
+        .unwrap_or(0)
+}
+
+///
+/// Returns the points spent in the tiers of a tree below `tier`.
+///
+fn points_below(save: &WillowTwoPlayerSaveGame, tree: &SkillTree, tier: i32) -> i32 {
+    tree.skills
+        .iter()
+        .filter(|skill| skill.tier < tier)
+        .map(|skill| skill_rank(save, skill.name))
+        .sum()
+}
+
+///
+/// Returns the points spent in a tree.
+///
+pub fn tree_points(save: &WillowTwoPlayerSaveGame, tree: &SkillTree) -> i32 {
+    points_below(save, tree, TIERS + 1)
+}
+
+///
+/// Returns the skills of a tree whose tier is no longer unlocked.
+///
+fn locked_skills(save: &WillowTwoPlayerSaveGame, tree: &SkillTree) -> Vec<&'static str> {
+    tree.skills
+        .iter()
+        .filter(|skill| {
+            skill_rank(save, skill.name) > 0
+                && points_below(save, tree, skill.tier) < tier_threshold(skill.tier)
+        })
+        .map(|skill| skill.name)
+        .collect()
+}
+
+pub(crate) fn set_grade(save: &mut WillowTwoPlayerSaveGame, skill: &str, rank: i32) {
+    match save.SkillData.iter_mut().find(|data| data.Skill() == skill) {
+        Some(data) => data.set_Grade(rank),
+        None => {
+            let mut data = SkillData::new();
+            data.set_Skill(skill.to_string());
+            data.set_Grade(rank);
+            data.set_GradePoints(0);
+            data.set_EquippedSlotIndex(0);
+            save.SkillData.push(data);
+        }
+    }
+}
+
+///
+/// Sets the rank of a skill and pays for it with `GeneralSkillPoints`.
+///
+/// The rank has to be unlocked by the points spent in the lower tiers of the tree, and
+/// lowering a rank must not lock a skill of a higher tier. The action skill only has the
+/// ranks 0 and 1 and is paid for like any other skill.
+///
+pub fn set_skill_rank(
+    save: &mut WillowTwoPlayerSaveGame,
+    skill: &str,
+    rank: i32,
+) -> Result<(), SkillError> {
+    let class = class_skills(save.PlayerClass()).ok_or_else(|| SkillError::UnknownClass {
+        msg: format!("{} has no known skill trees!", save.PlayerClass()),
+    })?;
+
+    let max_rank = match class.skill(skill) {
+        Some((_, definition)) => definition.max_rank,
+        None if skill == class.action_skill => 1,
+        None => {
+            return Err(SkillError::UnknownSkill {
+                msg: format!("{} is not a skill of {}!", skill, class.name),
+            })
+        }
+    };
+    if !(0..=max_rank).contains(&rank) {
+        return Err(SkillError::InvalidRank {
+            msg: format!("{} has ranks 0 to {}, not {}!", skill, max_rank, rank),
+        });
+    }
+
+    let cost = rank - skill_rank(save, skill);
+    if cost > save.GeneralSkillPoints() {
+        return Err(SkillError::NotEnoughSkillPoints {
+            msg: format!(
+                "{} needs {} skill points, {} are available!",
+                skill,
+                cost,
+                save.GeneralSkillPoints()
+            ),
+        });
+    }
+    let (tree, definition) = match class.skill(skill) {
+        Some(skill) => skill,
+        None => {
+            // The action skill is not part of a tree and has no tier to unlock.
+            set_grade(save, skill, rank);
+            save.set_GeneralSkillPoints(save.GeneralSkillPoints() - cost);
+            return Ok(());
+        }
+    };
+    if rank > 0 && points_below(save, tree, definition.tier) < tier_threshold(definition.tier) {
+        return Err(SkillError::TierLocked {
+            msg: format!(
+                "Tier {} of {} needs {} points in the tiers above!",
+                definition.tier,
+                tree.name,
+                tier_threshold(definition.tier)
+            ),
+        });
+    }
+
+    let mut working = save.clone();
+    set_grade(&mut working, skill, rank);
+    let locked = locked_skills(&working, tree);
+    if !locked.is_empty() {
+        return Err(SkillError::TierLocked {
+            msg: format!("Lowering {} locks {}!", skill, locked.join(", ")),
+        });
+    }
+    working.set_GeneralSkillPoints(working.GeneralSkillPoints() - cost);
+    *save = working;
+    Ok(())
+}
+
+///
+/// Resets all skills like the Quick Change respec and returns the refunded skill points.
+///
+/// Every skill including the action skill drops to rank 0, the equipped slots are cleared
+/// and `GeneralSkillPoints` is set to the points earned up to `ExpLevel`. Works for every
+/// class, including DLC classes without known skill trees.
+///
+pub fn respec(save: &mut WillowTwoPlayerSaveGame) -> i32 {
+    for data in save.SkillData.iter_mut() {
+        data.set_Grade(0);
+        data.set_GradePoints(0);
+        data.set_EquippedSlotIndex(0);
+    }
+    let points = experience::skill_points_for_level(save.ExpLevel());
+    let refunded = points - save.GeneralSkillPoints();
+    save.set_GeneralSkillPoints(points);
+    refunded
+}
+
+const fn skill(name: &'static str, tier: i32, max_rank: i32) -> SkillDefinition {
+    SkillDefinition {
+        name,
+        tier,
+        max_rank,
+    }
+}
+
+static CLASSES: &[ClassSkills] = &[
+    ClassSkills {
+        class: "GD_Soldier.Character.CharClass_Soldier",
+        name: "Commando",
+        action_skill: "GD_Soldier_Skills.ActionSkill.Skill_Scorpio",
+        trees: &[
+            SkillTree {
+                name: "Guerrilla",
+                skills: &[
+                    skill("GD_Soldier_Skills.Guerrilla.Sentry", 1, 5),
+                    skill("GD_Soldier_Skills.Guerrilla.Ready", 1, 5),
+                    skill("GD_Soldier_Skills.Guerrilla.LaserSight", 2, 5),
+                    skill("GD_Soldier_Skills.Guerrilla.Willing", 2, 5),
+                    skill("GD_Soldier_Skills.Guerrilla.Onslaught", 2, 5),
+                    skill("GD_Soldier_Skills.Guerrilla.ScorchedEarth", 3, 1),
+                    skill("GD_Soldier_Skills.Guerrilla.Able", 3, 5),
+                    skill("GD_Soldier_Skills.Guerrilla.Grenadier", 4, 5),
+                    skill("GD_Soldier_Skills.Guerrilla.DoubleUp", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Gunpowder",
+                skills: &[
+                    skill("GD_Soldier_Skills.Gunpowder.Impact", 1, 5),
+                    skill("GD_Soldier_Skills.Gunpowder.Expertise", 1, 5),
+                    skill("GD_Soldier_Skills.Gunpowder.Overload", 2, 5),
+                    skill("GD_Soldier_Skills.Gunpowder.MetalStorm", 2, 5),
+                    skill("GD_Soldier_Skills.Gunpowder.Steady", 2, 5),
+                    skill("GD_Soldier_Skills.Gunpowder.LongbowTurret", 3, 1),
+                    skill("GD_Soldier_Skills.Gunpowder.Ranger", 3, 5),
+                    skill("GD_Soldier_Skills.Gunpowder.DutyCalls", 4, 5),
+                    skill("GD_Soldier_Skills.Gunpowder.DoOrDie", 4, 1),
+                    skill("GD_Soldier_Skills.Gunpowder.Battlefront", 5, 5),
+                    skill("GD_Soldier_Skills.Gunpowder.Nuke", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Survival",
+                skills: &[
+                    skill("GD_Soldier_Skills.Survival.Healthy", 1, 5),
+                    skill("GD_Soldier_Skills.Survival.Preparation", 1, 5),
+                    skill("GD_Soldier_Skills.Survival.LastDitchEffort", 2, 5),
+                    skill("GD_Soldier_Skills.Survival.Pressure", 2, 5),
+                    skill("GD_Soldier_Skills.Survival.Forbearance", 2, 5),
+                    skill("GD_Soldier_Skills.Survival.PhalanxShield", 3, 1),
+                    skill("GD_Soldier_Skills.Survival.Resourceful", 3, 5),
+                    skill("GD_Soldier_Skills.Survival.MagLock", 4, 1),
+                    skill("GD_Soldier_Skills.Survival.Grit", 4, 5),
+                    skill("GD_Soldier_Skills.Survival.QuickCharge", 5, 5),
+                    skill("GD_Soldier_Skills.Survival.CrisisManagement", 5, 5),
+                    skill("GD_Soldier_Skills.Survival.Gemini", 6, 1),
+                ],
+            },
+        ],
+    },
+    ClassSkills {
+        class: "GD_Siren.Character.CharClass_Siren",
+        name: "Siren",
+        action_skill: "GD_Siren_Skills.ActionSkill.Skill_Phaselock",
+        trees: &[
+            SkillTree {
+                name: "Motion",
+                skills: &[
+                    skill("GD_Siren_Skills.Motion.Ward", 1, 5),
+                    skill("GD_Siren_Skills.Motion.Accelerate", 1, 5),
+                    skill("GD_Siren_Skills.Motion.Suspension", 2, 5),
+                    skill("GD_Siren_Skills.Motion.KineticReflection", 2, 5),
+                    skill("GD_Siren_Skills.Motion.Fleet", 3, 5),
+                    skill("GD_Siren_Skills.Motion.Converge", 3, 1),
+                    skill("GD_Siren_Skills.Motion.Inertia", 4, 5),
+                    skill("GD_Siren_Skills.Motion.Quicken", 4, 5),
+                    skill("GD_Siren_Skills.Motion.SubSequence", 5, 1),
+                    skill("GD_Siren_Skills.Motion.Thoughtlock", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Harmony",
+                skills: &[
+                    skill("GD_Siren_Skills.Harmony.MindsEye", 1, 5),
+                    skill("GD_Siren_Skills.Harmony.SweetRelease", 1, 5),
+                    skill("GD_Siren_Skills.Harmony.Restoration", 2, 5),
+                    skill("GD_Siren_Skills.Harmony.Wreck", 2, 5),
+                    skill("GD_Siren_Skills.Harmony.Elated", 3, 5),
+                    skill("GD_Siren_Skills.Harmony.Res", 3, 1),
+                    skill("GD_Siren_Skills.Harmony.Recompense", 4, 5),
+                    skill("GD_Siren_Skills.Harmony.Sustenance", 4, 5),
+                    skill("GD_Siren_Skills.Harmony.LifeTap", 5, 5),
+                    skill("GD_Siren_Skills.Harmony.Scorn", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Cataclysm",
+                skills: &[
+                    skill("GD_Siren_Skills.Cataclysm.Flicker", 1, 5),
+                    skill("GD_Siren_Skills.Cataclysm.Foresight", 1, 5),
+                    skill("GD_Siren_Skills.Cataclysm.Immolate", 2, 5),
+                    skill("GD_Siren_Skills.Cataclysm.Helios", 2, 5),
+                    skill("GD_Siren_Skills.Cataclysm.ChainReaction", 3, 5),
+                    skill("GD_Siren_Skills.Cataclysm.CloudKill", 3, 1),
+                    skill("GD_Siren_Skills.Cataclysm.Backdraft", 4, 5),
+                    skill("GD_Siren_Skills.Cataclysm.Reaper", 4, 5),
+                    skill("GD_Siren_Skills.Cataclysm.BlightPhoenix", 5, 5),
+                    skill("GD_Siren_Skills.Cataclysm.Ruin", 6, 1),
+                ],
+            },
+        ],
+    },
+    ClassSkills {
+        class: "GD_Mercenary.Character.CharClass_Mercenary",
+        name: "Gunzerker",
+        action_skill: "GD_Mercenary_Skills.ActionSkill.Skill_Gunzerking",
+        trees: &[
+            SkillTree {
+                name: "Gun Lust",
+                skills: &[
+                    skill("GD_Mercenary_Skills.Gun_Lust.LockedAndLoaded", 1, 5),
+                    skill("GD_Mercenary_Skills.Gun_Lust.QuickDraw", 1, 5),
+                    skill("GD_Mercenary_Skills.Gun_Lust.ImYourHuckleberry", 2, 5),
+                    skill("GD_Mercenary_Skills.Gun_Lust.AllINeedIsOne", 2, 5),
+                    skill("GD_Mercenary_Skills.Gun_Lust.DivergentLikeness", 3, 5),
+                    skill("GD_Mercenary_Skills.Gun_Lust.DownNotOut", 3, 1),
+                    skill("GD_Mercenary_Skills.Gun_Lust.AutoLoader", 4, 5),
+                    skill("GD_Mercenary_Skills.Gun_Lust.KeepItPipingHot", 4, 5),
+                    skill("GD_Mercenary_Skills.Gun_Lust.NoKillLikeOverkill", 4, 5),
+                    skill("GD_Mercenary_Skills.Gun_Lust.MoneyShot", 5, 1),
+                    skill("GD_Mercenary_Skills.Gun_Lust.LayWaste", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Rampage",
+                skills: &[
+                    skill("GD_Mercenary_Skills.Rampage.Inconceivable", 1, 5),
+                    skill("GD_Mercenary_Skills.Rampage.FilledToTheBrim", 1, 5),
+                    skill("GD_Mercenary_Skills.Rampage.AllInTheReflexes", 2, 5),
+                    skill("GD_Mercenary_Skills.Rampage.LastLonger", 2, 5),
+                    skill("GD_Mercenary_Skills.Rampage.ImReadyAlready", 2, 5),
+                    skill("GD_Mercenary_Skills.Rampage.SteadyAsSheGoes", 3, 5),
+                    skill("GD_Mercenary_Skills.Rampage.FiveShotsOrSix", 3, 1),
+                    skill("GD_Mercenary_Skills.Rampage.DoubleYourFun", 4, 1),
+                    skill("GD_Mercenary_Skills.Rampage.GetSome", 4, 5),
+                    skill("GD_Mercenary_Skills.Rampage.YippeeKiYay", 5, 5),
+                    skill("GD_Mercenary_Skills.Rampage.KeepFiring", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Brawn",
+                skills: &[
+                    skill("GD_Mercenary_Skills.Brawn.HardToKill", 1, 5),
+                    skill("GD_Mercenary_Skills.Brawn.Incite", 1, 5),
+                    skill("GD_Mercenary_Skills.Brawn.Asbestos", 2, 5),
+                    skill("GD_Mercenary_Skills.Brawn.ImTheJuggernaut", 2, 5),
+                    skill("GD_Mercenary_Skills.Brawn.OutOfBubblegum", 2, 1),
+                    skill("GD_Mercenary_Skills.Brawn.FistfulOfHurt", 3, 1),
+                    skill("GD_Mercenary_Skills.Brawn.SexualTyrannosaurus", 3, 5),
+                    skill("GD_Mercenary_Skills.Brawn.BusThatCantSlowDown", 4, 5),
+                    skill("GD_Mercenary_Skills.Brawn.AintGotTimeToBleed", 4, 5),
+                    skill("GD_Mercenary_Skills.Brawn.JustGotReal", 5, 5),
+                    skill("GD_Mercenary_Skills.Brawn.ComeAtMeBro", 6, 1),
+                ],
+            },
+        ],
+    },
+    ClassSkills {
+        class: "GD_Assassin.Character.CharClass_Assassin",
+        name: "Assassin",
+        action_skill: "GD_Assassin_Skills.ActionSkill.Skill_Deception",
+        trees: &[
+            SkillTree {
+                name: "Sniping",
+                skills: &[
+                    skill("GD_Assassin_Skills.Sniping.HeadShot", 1, 5),
+                    skill("GD_Assassin_Skills.Sniping.Optics", 1, 5),
+                    skill("GD_Assassin_Skills.Sniping.Killer", 2, 5),
+                    skill("GD_Assassin_Skills.Sniping.Precision", 2, 5),
+                    skill("GD_Assassin_Skills.Sniping.OneShotOneKill", 3, 5),
+                    skill("GD_Assassin_Skills.Sniping.Bore", 3, 1),
+                    skill("GD_Assassin_Skills.Sniping.Velocity", 3, 5),
+                    skill("GD_Assassin_Skills.Sniping.KillConfirmed", 4, 5),
+                    skill("GD_Assassin_Skills.Sniping.AtOneWithTheGun", 5, 5),
+                    skill("GD_Assassin_Skills.Sniping.CriticalAscention", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Cunning",
+                skills: &[
+                    skill("GD_Assassin_Skills.Cunning.FastHands", 1, 5),
+                    skill("GD_Assassin_Skills.Cunning.CounterStrike", 1, 5),
+                    skill("GD_Assassin_Skills.Cunning.Fearless", 2, 5),
+                    skill("GD_Assassin_Skills.Cunning.Ambush", 2, 5),
+                    skill("GD_Assassin_Skills.Cunning.RisingShot", 2, 5),
+                    skill("GD_Assassin_Skills.Cunning.DeathMark", 3, 1),
+                    skill("GD_Assassin_Skills.Cunning.Unforseen", 3, 5),
+                    skill("GD_Assassin_Skills.Cunning.Innervate", 4, 5),
+                    skill("GD_Assassin_Skills.Cunning.TwoFang", 5, 1),
+                    skill("GD_Assassin_Skills.Cunning.DeathBlossom", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Bloodshed",
+                skills: &[
+                    skill("GD_Assassin_Skills.Bloodshed.KillingBlow", 1, 5),
+                    skill("GD_Assassin_Skills.Bloodshed.IronHand", 1, 5),
+                    skill("GD_Assassin_Skills.Bloodshed.Grim", 2, 5),
+                    skill("GD_Assassin_Skills.Bloodshed.BeLikeWater", 2, 5),
+                    skill("GD_Assassin_Skills.Bloodshed.Followthrough", 2, 5),
+                    skill("GD_Assassin_Skills.Bloodshed.Execute", 3, 1),
+                    skill("GD_Assassin_Skills.Bloodshed.Backstab", 3, 5),
+                    skill("GD_Assassin_Skills.Bloodshed.Resurgence", 4, 5),
+                    skill("GD_Assassin_Skills.Bloodshed.LikeTheWind", 5, 5),
+                    skill("GD_Assassin_Skills.Bloodshed.ManyMustFall", 6, 1),
+                ],
+            },
+        ],
+    },
+    ClassSkills {
+        class: "GD_Tulip_Mechromancer.Character.CharClass_Mechromancer",
+        name: "Mechromancer",
+        action_skill: "GD_Tulip_Mechromancer_Skills.ActionSkill.Skill_DeathTrap",
+        trees: &[
+            SkillTree {
+                name: "Best Friends Forever",
+                skills: &[
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.CloseEnough",
+                        1,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.CookingUpTrouble",
+                        1,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.FancyMathematics",
+                        2,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.BuckUp",
+                        2,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.TheBetterHalf",
+                        2,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.UpshotRobot",
+                        3,
+                        1,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.PotentAsAPony",
+                        3,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.UnstoppableForce",
+                        4,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.ExplosiveClap",
+                        4,
+                        1,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.MadeOfSternerStuff",
+                        5,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.SharingIsCaring",
+                        5,
+                        1,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.BestFriendsForever.20PercentCooler",
+                        6,
+                        1,
+                    ),
+                ],
+            },
+            SkillTree {
+                name: "Little Big Trouble",
+                skills: &[
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.MorePep",
+                        1,
+                        5,
+                    ),
+                    skill("GD_Tulip_Mechromancer_Skills.LittleBigTrouble.Myelin", 1, 5),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.ShockStorm",
+                        2,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.TheStare",
+                        2,
+                        1,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.StrengthOfFiveGorillas",
+                        2,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.ElectricalBurn",
+                        3,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.ShockAndAAAGGGHHH",
+                        3,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.EvilEnchantress",
+                        4,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.OneTwoBoom",
+                        4,
+                        1,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.WiresDontTalk",
+                        5,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.InterspersedOutburst",
+                        5,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.LittleBigTrouble.MakeItSparkle",
+                        6,
+                        1,
+                    ),
+                ],
+            },
+            SkillTree {
+                name: "Ordered Chaos",
+                skills: &[
+                    skill("GD_Tulip_Mechromancer_Skills.OrderedChaos.Anarchy", 1, 1),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.OrderedChaos.SmallerLighterFaster",
+                        1,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.OrderedChaos.PreshrunkCyberpunk",
+                        2,
+                        1,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.OrderedChaos.BloodSoakedShields",
+                        2,
+                        5,
+                    ),
+                    skill("GD_Tulip_Mechromancer_Skills.OrderedChaos.Discord", 2, 1),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.OrderedChaos.TypecastIconoclast",
+                        3,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.OrderedChaos.RationalAnarchist",
+                        3,
+                        1,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.OrderedChaos.DeathFromAbove",
+                        4,
+                        5,
+                    ),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.OrderedChaos.TheNthDegree",
+                        4,
+                        5,
+                    ),
+                    skill("GD_Tulip_Mechromancer_Skills.OrderedChaos.WithClaws", 5, 5),
+                    skill(
+                        "GD_Tulip_Mechromancer_Skills.OrderedChaos.RobotRampage",
+                        6,
+                        1,
+                    ),
+                ],
+            },
+        ],
+    },
+    ClassSkills {
+        class: "GD_Lilac_PlayerClass.Character.CharClass_LilacPlayerClass",
+        name: "Psycho",
+        action_skill: "GD_Lilac_Skills_Mania.ActionSkill.Skill_BuzzaxeRampage",
+        trees: &[
+            SkillTree {
+                name: "Bloodlust",
+                skills: &[
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.BloodfilledGuns", 1, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.BloodyTwitch", 1, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.BuzzAxeBombardier", 2, 1),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.TasteOfBlood", 2, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.BloodOverdrive", 2, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.BloodyRevival", 3, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.NervousBlood", 3, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.BoilingBlood", 4, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.FuelTheBlood", 4, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.BloodBath", 5, 5),
+                    skill("GD_Lilac_Skills_Bloodlust.Skills.Bloodsplosion", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Mania",
+                skills: &[
+                    skill("GD_Lilac_Skills_Mania.Skills.EmptyTheRage", 1, 5),
+                    skill("GD_Lilac_Skills_Mania.Skills.FeedTheMeat", 1, 5),
+                    skill("GD_Lilac_Skills_Mania.Skills.PullThePin", 2, 1),
+                    skill("GD_Lilac_Skills_Mania.Skills.EmbraceThePain", 2, 5),
+                    skill("GD_Lilac_Skills_Mania.Skills.ThrillOfTheKill", 2, 5),
+                    skill("GD_Lilac_Skills_Mania.Skills.LightTheFuse", 3, 1),
+                    skill("GD_Lilac_Skills_Mania.Skills.StripTheFlesh", 3, 5),
+                    skill("GD_Lilac_Skills_Mania.Skills.Redrum", 4, 5),
+                    skill("GD_Lilac_Skills_Mania.Skills.FuelTheRampage", 4, 5),
+                    skill("GD_Lilac_Skills_Mania.Skills.SilenceTheVoices", 5, 5),
+                    skill("GD_Lilac_Skills_Mania.Skills.ReleaseTheBeast", 6, 1),
+                ],
+            },
+            SkillTree {
+                name: "Hellborn",
+                skills: &[
+                    skill("GD_Lilac_Skills_Hellborn.Skills.BurnBabyBurn", 1, 5),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.FuelTheFire", 1, 5),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.NumbedNerves", 2, 5),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.PainIsPower", 2, 5),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.HellfireHalitosis", 3, 1),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.DelusionalDamage", 3, 5),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.FlameFlare", 4, 5),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.FireFiend", 4, 5),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.ElementalEmpathy", 5, 5),
+                    skill("GD_Lilac_Skills_Hellborn.Skills.RavingRetribution", 6, 1),
+                ],
+            },
+        ],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        class_skills, classes, respec, set_skill_rank, skill_rank, tree_points, SkillError,
+    };
+    use crate::load_test_save;
+
+    #[test]
+    fn class_skills_test() {
+        let save_game = load_test_save();
+        let class = class_skills(save_game.PlayerClass()).unwrap();
+        for data in &save_game.SkillData {
+            assert!(data.Skill() == class.action_skill || class.skill(data.Skill()).is_some());
+        }
+        assert_eq!(tree_points(&save_game, &class.trees[0]), 32);
+        assert_eq!(tree_points(&save_game, &class.trees[1]), 0);
+    }
+
+    #[test]
+    fn tree_sizes_test() {
+        let sizes: Vec<(&str, Vec<usize>)> = classes()
+            .iter()
+            .map(|class| {
+                let sizes = class.trees.iter().map(|tree| tree.skills.len()).collect();
+                (class.name, sizes)
+            })
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                ("Commando", vec![9, 11, 12]),
+                ("Siren", vec![10, 10, 10]),
+                ("Gunzerker", vec![11, 11, 11]),
+                ("Assassin", vec![10, 10, 10]),
+                ("Mechromancer", vec![12, 12, 11]),
+                ("Psycho", vec![11, 11, 10]),
+            ]
+        );
+
+        let mechromancer =
+            class_skills("GD_Tulip_Mechromancer.Character.CharClass_Mechromancer").unwrap();
+        for skill in ["Anarchy", "Discord", "WithClaws"] {
+            let name = format!("GD_Tulip_Mechromancer_Skills.OrderedChaos.{}", skill);
+            assert!(mechromancer.trees[2].skill(&name).is_some());
+        }
+        assert!(mechromancer.trees[1]
+            .skill("GD_Tulip_Mechromancer_Skills.LittleBigTrouble.Myelin")
+            .is_some());
+    }
+
+    #[test]
+    fn set_skill_rank_test() {
+        let mut save_game = load_test_save();
+        save_game.set_GeneralSkillPoints(5);
+        assert!(matches!(
+            set_skill_rank(&mut save_game, "GD_Assassin_Skills.Cunning.Fearless", 1),
+            Err(SkillError::TierLocked { .. })
+        ));
+        assert!(matches!(
+            set_skill_rank(&mut save_game, "GD_Assassin_Skills.Cunning.FastHands", 6),
+            Err(SkillError::InvalidRank { .. })
+        ));
+        set_skill_rank(&mut save_game, "GD_Assassin_Skills.Cunning.FastHands", 5).unwrap();
+        assert_eq!(save_game.GeneralSkillPoints(), 0);
+        assert!(matches!(
+            set_skill_rank(&mut save_game, "GD_Assassin_Skills.Cunning.Fearless", 1),
+            Err(SkillError::NotEnoughSkillPoints { .. })
+        ));
+
+        set_skill_rank(&mut save_game, "GD_Assassin_Skills.Sniping.HeadShot", 3).unwrap();
+        assert_eq!(save_game.GeneralSkillPoints(), 2);
+        assert!(matches!(
+            set_skill_rank(&mut save_game, "GD_Assassin_Skills.Sniping.Optics", 0),
+            Err(SkillError::TierLocked { .. })
+        ));
+        assert_eq!(
+            skill_rank(&save_game, "GD_Assassin_Skills.Sniping.Optics"),
+            2
+        );
+
+        let action_skill = class_skills(save_game.PlayerClass()).unwrap().action_skill;
+        set_skill_rank(&mut save_game, action_skill, 0).unwrap();
+        assert_eq!(save_game.GeneralSkillPoints(), 3);
+        assert!(matches!(
+            set_skill_rank(&mut save_game, action_skill, 2),
+            Err(SkillError::InvalidRank { .. })
+        ));
+        set_skill_rank(&mut save_game, action_skill, 1).unwrap();
+        assert_eq!(save_game.GeneralSkillPoints(), 2);
+        assert_eq!(skill_rank(&save_game, action_skill), 1);
+    }
+
+    #[test]
+    fn respec_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(respec(&mut save_game), 33);
+        assert_eq!(save_game.GeneralSkillPoints(), 33);
+        let class = class_skills(save_game.PlayerClass()).unwrap();
+        assert_eq!(skill_rank(&save_game, class.action_skill), 0);
+        assert!(class
+            .trees
+            .iter()
+            .all(|tree| tree_points(&save_game, tree) == 0));
+
+        save_game.set_PlayerClass("GD_Unknown.Character.CharClass_Unknown".to_string());
+        save_game.set_IsDLCPlayerClass(true);
+        save_game.SkillData[0].set_Grade(1);
+        save_game.SkillData[1].set_Grade(5);
+        save_game.set_GeneralSkillPoints(27);
+        assert_eq!(respec(&mut save_game), 6);
+        assert_eq!(save_game.GeneralSkillPoints(), 33);
+        assert_eq!(save_game.SkillData[0].Grade(), 0);
+        assert_eq!(save_game.SkillData[1].Grade(), 0);
+    }
+}
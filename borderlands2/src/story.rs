@@ -0,0 +1,438 @@
+use crate::missions::{self, MissionError};
+use crate::playthrough::{self, Playthrough};
+use crate::regions::{self, RegionError};
+use crate::travel::{self, TravelError};
+use crate::MissionStatus::MissionStatus;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+#[derive(Debug)]
+pub enum StoryError {
+    NotAStoryMission { msg: String },
+    Locked { msg: String },
+    MissionError { msg: String },
+    RegionError { msg: String },
+    TravelError { msg: String },
+}
+
+impl std::fmt::Display for StoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StoryError::NotAStoryMission { msg } => write!(f, "NotAStoryMission: {}", msg),
+            StoryError::Locked { msg } => write!(f, "Locked: {}", msg),
+            StoryError::MissionError { msg } => write!(f, "MissionError: {}", msg),
+            StoryError::RegionError { msg } => write!(f, "RegionError: {}", msg),
+            StoryError::TravelError { msg } => write!(f, "TravelError: {}", msg),
+        }
+    }
+}
+
+impl From<RegionError> for StoryError {
+    fn from(err: RegionError) -> Self {
+        StoryError::RegionError {
+            msg: err.to_string(),
+        }
+    }
+}
+
+impl From<TravelError> for StoryError {
+    fn from(err: TravelError) -> Self {
+        StoryError::TravelError {
+            msg: err.to_string(),
+        }
+    }
+}
+
+impl From<MissionError> for StoryError {
+    fn from(err: MissionError) -> Self {
+        StoryError::MissionError {
+            msg: err.to_string(),
+        }
+    }
+}
+
+///
+/// What a plot mission opens up once the character reaches it.
+///
+#[derive(Debug)]
+pub struct StoryPoint {
+    /// Full path of the plot mission, see `missions::story_missions`.
+    pub mission: &'static str,
+    /// Fast travel stations as stored in `VisitedTeleporters`.
+    pub teleporters: &'static [&'static str],
+    /// Maps whose discoveries are uncovered, see `travel::maps`.
+    pub maps: &'static [&'static str],
+    /// Regions entered with their game stage in every playthrough, indexed by
+    /// `Playthrough::index`. `None` where the stage is not known, the game sets it when the
+    /// region is entered. The game picks it from the character level on the first visit, e.g.
+    /// Save0001 entered the Normal Mode Sanctuary at 30, so only the True Vault Hunter Mode
+    /// stages that save recorded are filled in.
+    pub regions: &'static [(&'static str, [Option<i32>; 3])],
+}
+
+///
+/// Returns the story point of a plot mission.
+///
+pub fn story_point(mission: &str) -> Option<&'static StoryPoint> {
+    STORY_POINTS.iter().find(|point| point.mission == mission)
+}
+
+///
+/// Returns the position of a mission in the main plot, starting at 0.
+///
+pub fn plot_mission_number(mission: &str) -> Option<i32> {
+    missions::story_missions()
+        .iter()
+        .position(|definition| definition.name == mission)
+        .map(|index| index as i32)
+}
+
+///
+/// Parks a playthrough right at the start of a plot mission.
+///
+/// Earlier plot missions are completed, later ones are dropped and the mission itself becomes
+/// the active one. The teleporters, map discoveries and known region game stages of every
+/// story point up to the mission are unlocked. When the playthrough is the current one,
+/// `PlotMissionNumber` is set to the last completed plot mission, see `plot_mission_number`.
+///
+/// `ActiveMissionNumber` is left to the game, its numbering is not known. Save0001 stores 38
+/// while its active missions are entry 24 and 28 of the tracked missions and plot mission 8,
+/// so it is neither a position in `MissionData` nor in the main plot.
+///
+pub fn jump_to_story_point(
+    save: &mut WillowTwoPlayerSaveGame,
+    playthrough: Playthrough,
+    mission: &str,
+) -> Result<(), StoryError> {
+    let plot_number = plot_mission_number(mission).ok_or_else(|| StoryError::NotAStoryMission {
+        msg: format!("{} is not a plot mission!", mission),
+    })?;
+    if !playthrough::is_unlocked(save, playthrough) {
+        return Err(StoryError::Locked {
+            msg: format!("{:?} is not unlocked!", playthrough),
+        });
+    }
+
+    let story = missions::story_missions();
+    let (reached, later) = story.split_at(plot_number as usize + 1);
+    let mut working = save.clone();
+    for definition in &reached[..reached.len() - 1] {
+        missions::set_mission_status(
+            &mut working,
+            playthrough,
+            definition.name,
+            MissionStatus::Complete,
+        )?;
+    }
+    missions::set_mission_status(&mut working, playthrough, mission, MissionStatus::Active)?;
+    missions::set_active_mission(&mut working, playthrough, mission)?;
+    working.MissionPlaythroughs[playthrough.index()]
+        .MissionData
+        .retain(|data| {
+            !later
+                .iter()
+                .any(|definition| definition.name == data.Mission())
+        });
+
+    for point in reached
+        .iter()
+        .filter_map(|definition| story_point(definition.name))
+    {
+        for teleporter in point.teleporters {
+            travel::unlock_teleporter(&mut working, teleporter)?;
+        }
+        for map in point.maps {
+            travel::reveal_map(&mut working, map)?;
+        }
+        for (region, stages) in point.regions {
+            if let Some(stage) = stages[playthrough.index()] {
+                regions::set_game_stage(&mut working, playthrough, region, stage)?;
+            }
+        }
+    }
+
+    if playthrough::current_playthrough(&working) == playthrough {
+        working.set_PlotMissionNumber((plot_number - 1).max(0));
+    }
+    *save = working;
+    Ok(())
+}
+
+static STORY_POINTS: &[StoryPoint] = &[
+    StoryPoint {
+        mission: "GD_Episode01.M_Ep1_Champion",
+        teleporters: &[],
+        maps: &["Glacial"],
+        regions: &[("GD_GameStages.Zone1.StartOfGame", [Some(1), Some(31), None])],
+    },
+    StoryPoint {
+        mission: "GD_Episode02.M_Ep2_Henchman",
+        teleporters: &[],
+        maps: &["SouthernShelf"],
+        regions: &[(
+            "GD_GameStages.Zone1.Waterfront_A",
+            [Some(3), Some(31), None],
+        )],
+    },
+    StoryPoint {
+        mission: "GD_Episode02.M_Ep2a_MoreGuns",
+        teleporters: &["SouthernShelfTown"],
+        maps: &[],
+        regions: &[(
+            "GD_GameStages.Zone1.Waterfront_A2",
+            [Some(4), Some(31), None],
+        )],
+    },
+    StoryPoint {
+        mission: "GD_Episode03.M_Ep3_CatchARide",
+        teleporters: &["IceEast"],
+        maps: &["Ice"],
+        regions: &[("GD_GameStages.Zone1.Ice_A", [Some(7), Some(33), None])],
+    },
+    StoryPoint {
+        mission: "GD_Episode04.M_Ep4_WelcomeToSanctuary",
+        teleporters: &["Sanctuary", "IceWest"],
+        maps: &["Sanctuary", "Frost"],
+        regions: &[
+            ("GD_GameStages.Zone1.Sanctuary", [Some(10), Some(37), None]),
+            ("GD_GameStages.Zone1.Ice_B", [Some(8), Some(34), None]),
+        ],
+    },
+    StoryPoint {
+        mission: "GD_Episode05.M_Ep5_ThePhoenix",
+        teleporters: &["IceCanyon"],
+        maps: &["IceCanyon"],
+        regions: &[("GD_GameStages.Zone1.Canyon", [Some(9), Some(35), None])],
+    },
+    StoryPoint {
+        mission: "GD_Episode06.M_Ep6_RescueRoland",
+        teleporters: &["GoshDam", "DamTop"],
+        maps: &["Dam", "DamTop"],
+        regions: &[
+            ("GD_GameStages.Zone1.Ice_C", [Some(12), Some(35), None]),
+            ("GD_GameStages.Zone1.Dam", [Some(12), Some(37), None]),
+        ],
+    },
+    StoryPoint {
+        mission: "GD_Episode07.M_Ep7_ATrainToCatch",
+        teleporters: &["TundraExpress", "Wilhelm"],
+        maps: &["TundraExpress", "TundraTrain"],
+        regions: &[
+            ("GD_GameStages.Zone1.Tundra", [Some(15), None, None]),
+            ("GD_GameStages.Zone1.TundraTrain", [Some(16), None, None]),
+        ],
+    },
+    StoryPoint {
+        mission: "GD_Episode08.M_Ep8_SanctuaryTakesOff",
+        teleporters: &[],
+        maps: &["SanctuaryAir"],
+        regions: &[(
+            "GD_GameStages.Zone2.Sanctuary_B",
+            [Some(16), Some(40), None],
+        )],
+    },
+    StoryPoint {
+        mission: "GD_Episode09.M_Ep9_GetBackToSanctuary",
+        teleporters: &["TheFridge", "Outwash", "Grass_B"],
+        maps: &["Fridge", "Outwash", "Grass"],
+        regions: &[
+            ("GD_GameStages.Zone2.Fridge", [Some(17), None, None]),
+            ("GD_GameStages.Zone2.Grass_A", [Some(17), None, None]),
+            ("GD_GameStages.Zone2.Grass_B", [Some(18), None, None]),
+        ],
+    },
+    StoryPoint {
+        mission: "GD_Episode10.M_Ep10_BirdISTheWord",
+        teleporters: &["PandoraPark"],
+        maps: &["PandoraPark"],
+        regions: &[("GD_GameStages.Zone2.PandoraPark", [Some(19), None, None])],
+    },
+    StoryPoint {
+        mission: "GD_Episode11.M_Ep11_LikeATonOf",
+        teleporters: &["Grass_C"],
+        maps: &["Grass_Cliffs"],
+        regions: &[("GD_GameStages.Zone2.Grass_C", [Some(20), None, None])],
+    },
+    StoryPoint {
+        mission: "GD_Episode12.M_Ep12_BecomingJack",
+        teleporters: &["HyperionCity"],
+        maps: &["HyperionCity"],
+        regions: &[
+            ("GD_GameStages.Zone2.Hyperion_A", [Some(21), None, None]),
+            ("GD_GameStages.Zone2.Hyperion_B", [Some(22), None, None]),
+        ],
+    },
+    StoryPoint {
+        mission: "GD_Episode13.M_Ep13_KillAngel",
+        teleporters: &["Cliffs", "Cliffs_Boss"],
+        maps: &["Boss_Cliffs", "VOGChamber"],
+        regions: &[
+            ("GD_GameStages.Zone2.Cliffs_A", [Some(20), None, None]),
+            ("GD_GameStages.Zone2.Cliffs_B", [Some(24), None, None]),
+            ("GD_GameStages.Zone2.AngelsChamber", [Some(26), None, None]),
+        ],
+    },
+    StoryPoint {
+        mission: "GD_Episode16.M_Ep16_LockAndLoad",
+        teleporters: &["Ash"],
+        maps: &["Sanctuary_Hole", "Ash"],
+        regions: &[
+            ("GD_GameStages.Zone3.Sanctuary_C", [Some(25), None, None]),
+            ("GD_GameStages.Zone3.Ash_A", [Some(25), Some(47), None]),
+        ],
+    },
+    StoryPoint {
+        mission: "GD_Episode15.M_Ep15_CharacterAssassination",
+        teleporters: &["CraterLake", "OnFireStone", "FyrestoneOneWay"],
+        maps: &["CraterLake", "Fyrestone"],
+        regions: &[
+            ("GD_GameStages.Zone3.Ash_B", [Some(27), None, None]),
+            ("GD_GameStages.Zone3.CraterLake", [Some(27), None, None]),
+            ("GD_GameStages.Zone3.Ash_C", [Some(27), None, None]),
+            ("GD_GameStages.Zone3.OnFireStone", [Some(28), None, None]),
+        ],
+    },
+    StoryPoint {
+        mission: "GD_Episode17.M_Ep17_KillJack",
+        teleporters: &["FinalBossAscent", "Volcano_Boss_A"],
+        maps: &["FinalBossAscent", "Boss_Volcano"],
+        regions: &[
+            (
+                "GD_GameStages.Zone3.FinalBossAscent",
+                [Some(30), None, None],
+            ),
+            ("GD_GameStages.Zone3.FinalBoss", [Some(31), None, None]),
+        ],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{jump_to_story_point, plot_mission_number, StoryError, STORY_POINTS};
+    use crate::load_test_save;
+    use crate::missions;
+    use crate::playthrough::{self, Playthrough};
+    use crate::regions;
+    use crate::travel;
+    use crate::MissionStatus::MissionStatus;
+
+    #[test]
+    fn plot_mission_number_test() {
+        let save_game = load_test_save();
+        assert_eq!(plot_mission_number("GD_Episode01.M_Ep1_Champion"), Some(0));
+        assert_eq!(
+            plot_mission_number("GD_Episode05.M_Ep5_ThePhoenix"),
+            Some(save_game.PlotMissionNumber())
+        );
+        assert_eq!(plot_mission_number("GD_Z1_ThisTown.M_ThisTown"), None);
+        for point in STORY_POINTS {
+            assert!(plot_mission_number(point.mission).is_some());
+            assert!(point
+                .teleporters
+                .iter()
+                .all(|teleporter| travel::teleporter(teleporter).is_some()));
+            assert!(point.maps.iter().all(|map| travel::map(map).is_some()));
+        }
+    }
+
+    #[test]
+    fn story_point_stages_test() {
+        let save_game = load_test_save();
+        for point in STORY_POINTS {
+            for (region, stages) in point.regions {
+                if let Some(stage) = stages[Playthrough::TrueVaultHunter.index()] {
+                    assert_eq!(
+                        regions::game_stage(&save_game, Playthrough::TrueVaultHunter, region),
+                        Some(stage)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn jump_to_story_point_test() {
+        let mut save_game = load_test_save();
+        let angel = "GD_Episode13.M_Ep13_KillAngel";
+        assert!(matches!(
+            jump_to_story_point(
+                &mut save_game,
+                Playthrough::Normal,
+                "GD_Z1_ThisTown.M_ThisTown"
+            ),
+            Err(StoryError::NotAStoryMission { .. })
+        ));
+        assert!(matches!(
+            jump_to_story_point(&mut save_game, Playthrough::UltimateVaultHunter, angel),
+            Err(StoryError::Locked { .. })
+        ));
+
+        save_game.set_ExpLevel(50);
+        playthrough::unlock_playthrough(&mut save_game, Playthrough::UltimateVaultHunter).unwrap();
+        playthrough::set_current_playthrough(&mut save_game, Playthrough::UltimateVaultHunter)
+            .unwrap();
+        jump_to_story_point(&mut save_game, Playthrough::UltimateVaultHunter, angel).unwrap();
+        let tracked = missions::missions(&save_game, Playthrough::UltimateVaultHunter).unwrap();
+        assert_eq!(tracked.len(), 16);
+        assert_eq!(tracked[15], (angel, MissionStatus::Active));
+        assert_eq!(save_game.PlotMissionNumber(), 14);
+        assert_eq!(save_game.ActiveMissionNumber(), 38);
+        assert!(
+            missions::validate_missions(&save_game, Playthrough::UltimateVaultHunter)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            regions::game_stages(&save_game, Playthrough::UltimateVaultHunter),
+            vec![("GD_GameStages.Zone1.StartOfGame", 50)]
+        );
+        assert!(save_game
+            .WorldDiscoveryList
+            .iter()
+            .filter(|discovery| discovery.DiscoveryName().starts_with("VOGCHAMBER_"))
+            .all(|discovery| discovery.HasBeenUncovered()));
+        assert!(!save_game
+            .WorldDiscoveryList
+            .iter()
+            .filter(|discovery| discovery.DiscoveryName().starts_with("ASH_"))
+            .any(|discovery| discovery.HasBeenUncovered()));
+
+        jump_to_story_point(
+            &mut save_game,
+            Playthrough::Normal,
+            "GD_Episode06.M_Ep6_RescueRoland",
+        )
+        .unwrap();
+        let tracked = missions::missions(&save_game, Playthrough::Normal).unwrap();
+        assert_eq!(tracked.len(), 88);
+        assert_eq!(save_game.PlotMissionNumber(), 14);
+        assert_eq!(save_game.VisitedTeleporters.len(), 28);
+    }
+
+    #[test]
+    fn story_point_game_stages_test() {
+        let mut save_game = load_test_save();
+        let original = regions::game_stages(&save_game, Playthrough::TrueVaultHunter)
+            .into_iter()
+            .map(|(region, stage)| (region.to_string(), stage))
+            .collect::<Vec<_>>();
+
+        playthrough::reset_playthrough(&mut save_game, Playthrough::TrueVaultHunter).unwrap();
+        jump_to_story_point(
+            &mut save_game,
+            Playthrough::TrueVaultHunter,
+            "GD_Episode16.M_Ep16_LockAndLoad",
+        )
+        .unwrap();
+        for (region, stage) in regions::game_stages(&save_game, Playthrough::TrueVaultHunter) {
+            if let Some((_, expected)) = original.iter().find(|(name, _)| name == region) {
+                assert_eq!(stage, *expected, "{}", region);
+            }
+        }
+        let stage = |region| regions::game_stage(&save_game, Playthrough::TrueVaultHunter, region);
+        assert_eq!(stage("GD_GameStages.Zone1.Waterfront_A"), Some(31));
+        assert_eq!(stage("GD_GameStages.Zone1.Ice_A"), Some(33));
+        assert_eq!(stage("GD_GameStages.Zone2.Sanctuary_B"), Some(40));
+        assert_eq!(stage("GD_GameStages.Zone3.Ash_A"), Some(47));
+        assert_eq!(stage("GD_GameStages.Zone2.Hyperion_A"), None);
+    }
+}
@@ -0,0 +1,404 @@
+use std::collections::HashSet;
+
+use crate::bank;
+use crate::equipment::GearIndex;
+use crate::inventory::{self, InventoryError, Overflow, Placement};
+use crate::serial::{decode_serial, next_seed, reseed_serial, time_seed};
+use crate::PlayerMark::PlayerMark;
+use crate::QuickWeaponSlot::QuickWeaponSlot;
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+use protobuf::Enum;
+
+#[derive(Debug)]
+pub enum TransferError {
+    NoSuchGear { msg: String },
+    InvalidSerial { msg: String },
+    NoSpace { msg: String },
+}
+
+impl std::fmt::Display for TransferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TransferError::NoSuchGear { msg } => write!(f, "NoSuchGear: {}", msg),
+            TransferError::InvalidSerial { msg } => write!(f, "InvalidSerial: {}", msg),
+            TransferError::NoSpace { msg } => write!(f, "NoSpace: {}", msg),
+        }
+    }
+}
+
+impl From<InventoryError> for TransferError {
+    fn from(err: InventoryError) -> Self {
+        match err {
+            InventoryError::ConvertError { msg } => TransferError::InvalidSerial { msg },
+            InventoryError::NoSuchGear { msg } => TransferError::NoSuchGear { msg },
+            err => TransferError::NoSpace {
+                msg: err.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransferMode {
+    /// The gear stays in the source save.
+    Copy,
+    /// The gear is removed from the source save once it is stored in the target.
+    Move,
+}
+
+///
+/// Gear of the source save to transfer.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TransferSource {
+    /// Gear in the backpack.
+    Backpack(GearIndex),
+    /// Index into `BankSlots`.
+    Bank(usize),
+}
+
+impl From<GearIndex> for TransferSource {
+    fn from(gear: GearIndex) -> Self {
+        TransferSource::Backpack(gear)
+    }
+}
+
+///
+/// Options of a transfer between two saves.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TransferOptions {
+    pub mode: TransferMode,
+    /// What to do with gear that does not fit into the backpack of the target.
+    pub overflow: Overflow,
+    /// Seed of the first serial stored in the target, every further serial uses the next seed.
+    /// `None` derives the first seed from the current time.
+    pub seed: Option<i32>,
+    /// Mark of the gear in the target. `None` keeps the mark of the source, gear from the
+    /// bank is marked standard.
+    pub mark: Option<PlayerMark>,
+}
+
+impl Default for TransferOptions {
+    fn default() -> Self {
+        TransferOptions {
+            mode: TransferMode::Copy,
+            overflow: Overflow::Refuse,
+            seed: None,
+            mark: None,
+        }
+    }
+}
+
+///
+/// Outcome of a transfer.
+///
+#[derive(Debug, Default)]
+pub struct TransferReport {
+    /// Gear of the source and where it was stored in the target.
+    pub transferred: Vec<(TransferSource, Placement)>,
+    /// Gear of the source that was not transferred and why.
+    pub rejected: Vec<(TransferSource, TransferError)>,
+}
+
+fn transfer_one(
+    source: &WillowTwoPlayerSaveGame,
+    target: &mut WillowTwoPlayerSaveGame,
+    gear: TransferSource,
+    options: &TransferOptions,
+    seed: i32,
+) -> Result<Placement, TransferError> {
+    let no_such_gear = || TransferError::NoSuchGear {
+        msg: format!("{:?} does not exist in the source!", gear),
+    };
+    let reseed = |serial: &[u8]| {
+        reseed_serial(serial, seed).map_err(|err| TransferError::InvalidSerial {
+            msg: err.to_string(),
+        })
+    };
+    let overflow = match options.overflow {
+        Overflow::Refuse => Overflow::Refuse,
+        Overflow::Bank { .. } => Overflow::Bank { seed },
+    };
+
+    let placement = match gear {
+        TransferSource::Backpack(GearIndex::Weapon(index)) => {
+            let mut weapon = source
+                .WeaponData
+                .get(index)
+                .ok_or_else(no_such_gear)?
+                .clone();
+            weapon.set_QuickSlot(QuickWeaponSlot::None);
+            if let Some(mark) = options.mark {
+                weapon.set_Mark(mark);
+            }
+            inventory::add_weapon(target, weapon, overflow)?
+        }
+        TransferSource::Backpack(GearIndex::PackedWeapon(index)) => {
+            let mut weapon = source
+                .PackedWeaponData
+                .get(index)
+                .ok_or_else(no_such_gear)?
+                .clone();
+            weapon.set_InventorySerialNumber(reseed(weapon.InventorySerialNumber())?);
+            weapon.set_QuickSlot(QuickWeaponSlot::None);
+            if let Some(mark) = options.mark {
+                weapon.set_Mark(mark);
+            }
+            inventory::add_packed_weapon(target, weapon, overflow)?
+        }
+        TransferSource::Backpack(GearIndex::Item(index)) => {
+            let mut item = source.ItemData.get(index).ok_or_else(no_such_gear)?.clone();
+            item.set_Equipped(false);
+            if let Some(mark) = options.mark {
+                item.set_Mark(mark);
+            }
+            inventory::add_item(target, item, overflow)?
+        }
+        TransferSource::Backpack(GearIndex::PackedItem(index)) => {
+            let mut item = source
+                .PackedItemData
+                .get(index)
+                .ok_or_else(no_such_gear)?
+                .clone();
+            item.set_InventorySerialNumber(reseed(item.InventorySerialNumber())?);
+            item.set_Equipped(false);
+            if let Some(mark) = options.mark {
+                item.set_Mark(mark.value());
+            }
+            inventory::add_packed_item(target, item, overflow)?
+        }
+        TransferSource::Bank(index) => {
+            let serial = reseed(
+                source
+                    .BankSlots
+                    .get(index)
+                    .ok_or_else(no_such_gear)?
+                    .InventorySerialNumber(),
+            )?;
+            let is_weapon = decode_serial(&serial)
+                .map_err(|err| TransferError::InvalidSerial {
+                    msg: err.to_string(),
+                })?
+                .is_weapon;
+            let mark = options.mark.unwrap_or(PlayerMark::Standard);
+            if is_weapon {
+                inventory::add_packed_weapon(target, bank::bank_weapon(&serial, mark), overflow)?
+            } else {
+                inventory::add_packed_item(target, bank::bank_item(&serial, 1, mark), overflow)?
+            }
+        }
+    };
+    Ok(placement)
+}
+
+///
+/// Removes the given gear from its lists, highest index first so the indices stay valid.
+///
+fn remove_gear(save: &mut WillowTwoPlayerSaveGame, gear: &[TransferSource]) {
+    let mut gear = gear.to_vec();
+    gear.sort_by_key(|gear| match gear {
+        TransferSource::Backpack(GearIndex::Weapon(index))
+        | TransferSource::Backpack(GearIndex::PackedWeapon(index))
+        | TransferSource::Backpack(GearIndex::Item(index))
+        | TransferSource::Backpack(GearIndex::PackedItem(index))
+        | TransferSource::Bank(index) => std::cmp::Reverse(*index),
+    });
+    for gear in gear {
+        match gear {
+            TransferSource::Backpack(GearIndex::Weapon(index)) => {
+                save.WeaponData.remove(index);
+            }
+            TransferSource::Backpack(GearIndex::PackedWeapon(index)) => {
+                save.PackedWeaponData.remove(index);
+            }
+            TransferSource::Backpack(GearIndex::Item(index)) => {
+                save.ItemData.remove(index);
+            }
+            TransferSource::Backpack(GearIndex::PackedItem(index)) => {
+                save.PackedItemData.remove(index);
+            }
+            TransferSource::Bank(index) => {
+                save.BankSlots.remove(index);
+            }
+        }
+    }
+}
+
+///
+/// Copies or moves gear from the backpack or bank of one save into another.
+///
+/// Transferred gear is unequipped in the target and every packed serial is re-encrypted with
+/// its own seed, so a copy never shares a serial with the original. Gear that does not fit
+/// into the target or can not be re-encrypted is reported and stays in the source. When
+/// moving, the transferred gear is removed from the source, which shifts the index of the
+/// remaining gear.
+///
+pub fn transfer(
+    source: &mut WillowTwoPlayerSaveGame,
+    target: &mut WillowTwoPlayerSaveGame,
+    gear: &[TransferSource],
+    options: &TransferOptions,
+) -> TransferReport {
+    let mut report = TransferReport::default();
+    let mut seen = HashSet::new();
+    let mut seed = options.seed.unwrap_or_else(time_seed);
+    for gear in gear {
+        if !seen.insert(*gear) {
+            continue;
+        }
+        match transfer_one(source, target, *gear, options, seed) {
+            Ok(placement) => report.transferred.push((*gear, placement)),
+            Err(err) => report.rejected.push((*gear, err)),
+        }
+        seed = next_seed(seed);
+    }
+
+    if options.mode == TransferMode::Move {
+        let moved: Vec<TransferSource> = report.transferred.iter().map(|(gear, _)| *gear).collect();
+        remove_gear(source, &moved);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{transfer, TransferError, TransferMode, TransferOptions, TransferSource};
+    use crate::equipment::GearIndex;
+    use crate::inventory::{self, Overflow, Placement};
+    use crate::load_test_save;
+    use crate::serial::decode_serial;
+    use crate::PlayerMark::PlayerMark;
+    use crate::QuickWeaponSlot::QuickWeaponSlot;
+
+    #[test]
+    fn copy_reseed_test() {
+        let mut source = load_test_save();
+        let mut target = load_test_save();
+        let weapons = target.PackedWeaponData.len();
+
+        let options = TransferOptions {
+            seed: Some(0x1357_9bdf),
+            mark: Some(PlayerMark::Favorite),
+            ..Default::default()
+        };
+        let report = transfer(
+            &mut source,
+            &mut target,
+            &[GearIndex::PackedWeapon(0).into()],
+            &options,
+        );
+        assert!(report.rejected.is_empty());
+        assert_eq!(
+            report.transferred,
+            vec![(
+                TransferSource::Backpack(GearIndex::PackedWeapon(0)),
+                Placement::Backpack(GearIndex::PackedWeapon(weapons))
+            )]
+        );
+
+        let copied = &target.PackedWeaponData[weapons];
+        assert_eq!(copied.QuickSlot(), QuickWeaponSlot::None);
+        assert_eq!(copied.Mark(), PlayerMark::Favorite);
+        let original = decode_serial(source.PackedWeaponData[0].InventorySerialNumber()).unwrap();
+        let reseeded = decode_serial(copied.InventorySerialNumber()).unwrap();
+        assert_eq!(reseeded.seed, 0x1357_9bdf);
+        assert_eq!(reseeded.balance, original.balance);
+        assert_eq!(reseeded.parts, original.parts);
+    }
+
+    #[test]
+    fn move_overflow_test() {
+        let mut source = load_test_save();
+        let mut target = load_test_save();
+        let used = inventory::backpack_used(&target) as i32;
+        target
+            .InventorySlotData
+            .mut_or_insert_default()
+            .set_InventorySlotMax(used + 1);
+        target.BankSlots.clear();
+        target.set_MaxBankSlots(1);
+        let serial = source.PackedWeaponData[6].InventorySerialNumber().to_vec();
+
+        let options = TransferOptions {
+            mode: TransferMode::Move,
+            overflow: Overflow::Bank { seed: 0 },
+            ..Default::default()
+        };
+        let gear = [
+            GearIndex::PackedWeapon(4).into(),
+            GearIndex::PackedWeapon(5).into(),
+            GearIndex::PackedWeapon(6).into(),
+            GearIndex::PackedWeapon(99).into(),
+        ];
+        let report = transfer(&mut source, &mut target, &gear, &options);
+        assert_eq!(report.transferred.len(), 2);
+        assert_eq!(report.transferred[1].1, Placement::Bank(0));
+        assert!(matches!(
+            report.rejected[..],
+            [
+                (
+                    TransferSource::Backpack(GearIndex::PackedWeapon(6)),
+                    TransferError::NoSpace { .. }
+                ),
+                (
+                    TransferSource::Backpack(GearIndex::PackedWeapon(99)),
+                    TransferError::NoSuchGear { .. }
+                )
+            ]
+        ));
+        assert_eq!(source.PackedWeaponData.len(), 16);
+        assert_eq!(source.PackedWeaponData[4].InventorySerialNumber(), serial);
+    }
+
+    #[test]
+    fn transfer_bank_test() {
+        let mut source = load_test_save();
+        let mut target = load_test_save();
+        target.PackedWeaponData.clear();
+        target.PackedItemData.clear();
+        let banked = source.BankSlots[3].InventorySerialNumber().to_vec();
+
+        let options = TransferOptions {
+            mode: TransferMode::Move,
+            ..Default::default()
+        };
+        let gear = [
+            GearIndex::PackedWeapon(0).into(),
+            GearIndex::PackedWeapon(1).into(),
+            TransferSource::Bank(3),
+        ];
+        let report = transfer(&mut source, &mut target, &gear, &options);
+        assert!(report.rejected.is_empty());
+        assert_eq!(source.BankSlots.len(), 9);
+        assert!(source
+            .BankSlots
+            .iter()
+            .all(|slot| slot.InventorySerialNumber() != banked));
+
+        let serials: Vec<Vec<u8>> = target
+            .PackedWeaponData
+            .iter()
+            .map(|weapon| weapon.InventorySerialNumber().to_vec())
+            .chain(
+                target
+                    .PackedItemData
+                    .iter()
+                    .map(|item| item.InventorySerialNumber().to_vec()),
+            )
+            .collect();
+        assert_eq!(serials.len(), 3);
+        let seeds: Vec<i32> = serials
+            .iter()
+            .map(|serial| decode_serial(serial).unwrap().seed)
+            .collect();
+        assert!(seeds[0] != seeds[1] && seeds[1] != seeds[2] && seeds[0] != seeds[2]);
+        assert!(!serials.contains(&banked));
+        let mut original = decode_serial(&banked).unwrap();
+        let mut moved = decode_serial(&serials[2]).unwrap();
+        original.seed = 0;
+        moved.seed = 0;
+        assert_eq!(moved, original);
+    }
+}
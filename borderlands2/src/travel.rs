@@ -0,0 +1,545 @@
+use crate::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+#[derive(Debug)]
+pub enum TravelError {
+    UnknownTeleporter { msg: String },
+    UnknownMap { msg: String },
+    SpawnTeleporter { msg: String },
+}
+
+impl std::fmt::Display for TravelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TravelError::UnknownTeleporter { msg } => write!(f, "UnknownTeleporter: {}", msg),
+            TravelError::UnknownMap { msg } => write!(f, "UnknownMap: {}", msg),
+            TravelError::SpawnTeleporter { msg } => write!(f, "SpawnTeleporter: {}", msg),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Teleporter {
+    /// Name as stored in `VisitedTeleporters` and `LastVisitedTeleporter`.
+    pub name: &'static str,
+    pub display_name: &'static str,
+}
+
+#[derive(Debug)]
+pub struct MapDefinition {
+    /// Map name as used by the `WorldDiscoveryList` entries, see `discovery_map`.
+    pub name: &'static str,
+    pub display_name: &'static str,
+}
+
+impl MapDefinition {
+    ///
+    /// Returns the level package name as stored in `FullyExploredAreas`.
+    ///
+    pub fn area(&self) -> String {
+        format!("{}_P", self.name)
+    }
+}
+
+///
+/// Returns the fast travel stations of the base game.
+///
+pub fn teleporters() -> &'static [Teleporter] {
+    TELEPORTERS
+}
+
+///
+/// Returns the catalog entry of a fast travel station.
+///
+pub fn teleporter(name: &str) -> Option<&'static Teleporter> {
+    TELEPORTERS
+        .iter()
+        .find(|teleporter| teleporter.name == name)
+}
+
+///
+/// Returns the maps of the base game that have discoveries.
+///
+pub fn maps() -> &'static [MapDefinition] {
+    MAPS
+}
+
+///
+/// Returns the catalog entry of a map, the name is matched case insensitive.
+///
+pub fn map(name: &str) -> Option<&'static MapDefinition> {
+    MAPS.iter().find(|map| map.name.eq_ignore_ascii_case(name))
+}
+
+///
+/// Returns the map of a `WorldDiscoveryList` entry, e.g. `GLACIAL` for `GLACIAL_PWDA_1`.
+///
+pub fn discovery_map(discovery: &str) -> Option<&str> {
+    discovery.rsplitn(3, '_').nth(2)
+}
+
+fn known_teleporter(name: &str) -> Result<&'static Teleporter, TravelError> {
+    teleporter(name).ok_or_else(|| TravelError::UnknownTeleporter {
+        msg: format!("{} is not a fast travel station!", name),
+    })
+}
+
+fn known_map(name: &str) -> Result<&'static MapDefinition, TravelError> {
+    map(name).ok_or_else(|| TravelError::UnknownMap {
+        msg: format!("{} is not a map with discoveries!", name),
+    })
+}
+
+///
+/// Returns whether a fast travel station was visited.
+///
+pub fn is_unlocked(save: &WillowTwoPlayerSaveGame, teleporter: &str) -> bool {
+    save.VisitedTeleporters
+        .iter()
+        .any(|visited| visited == teleporter)
+}
+
+///
+/// Unlocks a fast travel station by adding it to `VisitedTeleporters`.
+///
+pub fn unlock_teleporter(
+    save: &mut WillowTwoPlayerSaveGame,
+    teleporter: &str,
+) -> Result<(), TravelError> {
+    let teleporter = known_teleporter(teleporter)?;
+    if !is_unlocked(save, teleporter.name) {
+        save.VisitedTeleporters.push(teleporter.name.to_string());
+    }
+    Ok(())
+}
+
+///
+/// Locks a fast travel station, the spawn teleporter can not be locked.
+///
+pub fn lock_teleporter(
+    save: &mut WillowTwoPlayerSaveGame,
+    teleporter: &str,
+) -> Result<(), TravelError> {
+    let teleporter = known_teleporter(teleporter)?;
+    if save.LastVisitedTeleporter() == teleporter.name {
+        return Err(TravelError::SpawnTeleporter {
+            msg: format!(
+                "{} is the spawn teleporter, choose another one first!",
+                teleporter.display_name
+            ),
+        });
+    }
+    save.VisitedTeleporters
+        .retain(|visited| visited != teleporter.name);
+    Ok(())
+}
+
+///
+/// Unlocks every fast travel station of the catalog.
+///
+pub fn unlock_all_teleporters(save: &mut WillowTwoPlayerSaveGame) {
+    for teleporter in TELEPORTERS {
+        if !is_unlocked(save, teleporter.name) {
+            save.VisitedTeleporters.push(teleporter.name.to_string());
+        }
+    }
+}
+
+///
+/// Locks every fast travel station except the spawn teleporter.
+///
+pub fn lock_all_teleporters(save: &mut WillowTwoPlayerSaveGame) {
+    let spawn = save.LastVisitedTeleporter().to_string();
+    save.VisitedTeleporters.retain(|visited| *visited == spawn);
+}
+
+///
+/// Returns the fast travel station the character spawns at.
+///
+pub fn spawn_teleporter(save: &WillowTwoPlayerSaveGame) -> &str {
+    save.LastVisitedTeleporter()
+}
+
+///
+/// Sets the fast travel station the character spawns at and unlocks it.
+///
+pub fn set_spawn_teleporter(
+    save: &mut WillowTwoPlayerSaveGame,
+    teleporter: &str,
+) -> Result<(), TravelError> {
+    unlock_teleporter(save, teleporter)?;
+    save.set_LastVisitedTeleporter(teleporter.to_string());
+    Ok(())
+}
+
+///
+/// Uncovers the `WorldDiscoveryList` entries of a map and returns how many were hidden.
+///
+pub fn reveal_map(save: &mut WillowTwoPlayerSaveGame, map: &str) -> Result<usize, TravelError> {
+    let map = known_map(map)?;
+    let mut revealed = 0;
+    for discovery in save.WorldDiscoveryList.iter_mut().filter(|discovery| {
+        discovery_map(discovery.DiscoveryName())
+            .is_some_and(|name| name.eq_ignore_ascii_case(map.name))
+    }) {
+        if !discovery.HasBeenUncovered() {
+            discovery.set_HasBeenUncovered(true);
+            revealed += 1;
+        }
+    }
+    Ok(revealed)
+}
+
+///
+/// Uncovers every `WorldDiscoveryList` entry and returns how many were hidden.
+///
+pub fn reveal_all_maps(save: &mut WillowTwoPlayerSaveGame) -> usize {
+    let mut revealed = 0;
+    for discovery in save
+        .WorldDiscoveryList
+        .iter_mut()
+        .filter(|discovery| !discovery.HasBeenUncovered())
+    {
+        discovery.set_HasBeenUncovered(true);
+        revealed += 1;
+    }
+    revealed
+}
+
+///
+/// Returns whether a map is listed in `FullyExploredAreas`.
+///
+pub fn is_fully_explored(save: &WillowTwoPlayerSaveGame, map: &str) -> bool {
+    map_entry(save, map).is_some()
+}
+
+fn map_entry(save: &WillowTwoPlayerSaveGame, map: &str) -> Option<usize> {
+    let area = format!("{}_P", map);
+    save.FullyExploredAreas
+        .iter()
+        .position(|explored| explored.eq_ignore_ascii_case(&area))
+}
+
+///
+/// Adds or removes a map from `FullyExploredAreas`.
+///
+pub fn set_fully_explored(
+    save: &mut WillowTwoPlayerSaveGame,
+    map: &str,
+    explored: bool,
+) -> Result<(), TravelError> {
+    let map = known_map(map)?;
+    match (map_entry(save, map.name), explored) {
+        (None, true) => save.FullyExploredAreas.push(map.area()),
+        (Some(index), false) => {
+            save.FullyExploredAreas.remove(index);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+static TELEPORTERS: &[Teleporter] = &[
+    Teleporter {
+        name: "GlacialIgloo",
+        display_name: "Windshear Waste",
+    },
+    Teleporter {
+        name: "SouthernShelfTown",
+        display_name: "Liar's Berg",
+    },
+    Teleporter {
+        name: "IceEast",
+        display_name: "Three Horns - Divide",
+    },
+    Teleporter {
+        name: "IceWest",
+        display_name: "Three Horns - Valley",
+    },
+    Teleporter {
+        name: "Sanctuary",
+        display_name: "Sanctuary",
+    },
+    Teleporter {
+        name: "SouthpawFactory",
+        display_name: "Southpaw Steam & Power",
+    },
+    Teleporter {
+        name: "IceCanyon",
+        display_name: "Frostburn Canyon",
+    },
+    Teleporter {
+        name: "Interlude",
+        display_name: "The Dust",
+    },
+    Teleporter {
+        name: "GoshDam",
+        display_name: "Bloodshot Stronghold",
+    },
+    Teleporter {
+        name: "DamTop",
+        display_name: "Bloodshot Ramparts",
+    },
+    Teleporter {
+        name: "TundraExpress",
+        display_name: "Tundra Express",
+    },
+    Teleporter {
+        name: "Wilhelm",
+        display_name: "End of the Line",
+    },
+    Teleporter {
+        name: "TheFridge",
+        display_name: "The Fridge",
+    },
+    Teleporter {
+        name: "Outwash",
+        display_name: "Highlands - Outwash",
+    },
+    Teleporter {
+        name: "Grass_B",
+        display_name: "The Highlands",
+    },
+    Teleporter {
+        name: "Grass_C",
+        display_name: "The Highlands - Overlook",
+    },
+    Teleporter {
+        name: "Caverns",
+        display_name: "Caustic Caverns",
+    },
+    Teleporter {
+        name: "PandoraPark",
+        display_name: "Wildlife Exploitation Preserve",
+    },
+    Teleporter {
+        name: "Cliffs",
+        display_name: "Thousand Cuts",
+    },
+    Teleporter {
+        name: "Cliffs_Boss",
+        display_name: "The Bunker",
+    },
+    Teleporter {
+        name: "Lynchwood",
+        display_name: "Lynchwood",
+    },
+    Teleporter {
+        name: "HyperionCity",
+        display_name: "Opportunity",
+    },
+    Teleporter {
+        name: "Ash",
+        display_name: "Eridium Blight",
+    },
+    Teleporter {
+        name: "CraterLake",
+        display_name: "Sawtooth Cauldron",
+    },
+    Teleporter {
+        name: "OnFireStone",
+        display_name: "Arid Nexus - Boneyard",
+    },
+    Teleporter {
+        name: "FyrestoneOneWay",
+        display_name: "Arid Nexus - Badlands",
+    },
+    Teleporter {
+        name: "FinalBossAscent",
+        display_name: "Hero's Pass",
+    },
+    Teleporter {
+        name: "Volcano_Boss_A",
+        display_name: "Vault of the Warrior",
+    },
+];
+
+static MAPS: &[MapDefinition] = &[
+    MapDefinition {
+        name: "Glacial",
+        display_name: "Windshear Waste",
+    },
+    MapDefinition {
+        name: "SouthernShelf",
+        display_name: "Southern Shelf",
+    },
+    MapDefinition {
+        name: "Cove",
+        display_name: "Southern Shelf - Bay",
+    },
+    MapDefinition {
+        name: "Ice",
+        display_name: "Three Horns - Divide",
+    },
+    MapDefinition {
+        name: "Frost",
+        display_name: "Three Horns - Valley",
+    },
+    MapDefinition {
+        name: "Sanctuary",
+        display_name: "Sanctuary",
+    },
+    MapDefinition {
+        name: "SouthpawFactory",
+        display_name: "Southpaw Steam & Power",
+    },
+    MapDefinition {
+        name: "IceCanyon",
+        display_name: "Frostburn Canyon",
+    },
+    MapDefinition {
+        name: "Interlude",
+        display_name: "The Dust",
+    },
+    MapDefinition {
+        name: "Dam",
+        display_name: "Bloodshot Stronghold",
+    },
+    MapDefinition {
+        name: "DamTop",
+        display_name: "Bloodshot Ramparts",
+    },
+    MapDefinition {
+        name: "TundraExpress",
+        display_name: "Tundra Express",
+    },
+    MapDefinition {
+        name: "TundraTrain",
+        display_name: "End of the Line",
+    },
+    MapDefinition {
+        name: "SanctuaryAir",
+        display_name: "Sanctuary (Airborne)",
+    },
+    MapDefinition {
+        name: "Fridge",
+        display_name: "The Fridge",
+    },
+    MapDefinition {
+        name: "Outwash",
+        display_name: "Highlands - Outwash",
+    },
+    MapDefinition {
+        name: "Grass",
+        display_name: "The Highlands",
+    },
+    MapDefinition {
+        name: "Caverns",
+        display_name: "Caustic Caverns",
+    },
+    MapDefinition {
+        name: "PandoraPark",
+        display_name: "Wildlife Exploitation Preserve",
+    },
+    MapDefinition {
+        name: "Grass_Cliffs",
+        display_name: "Thousand Cuts",
+    },
+    MapDefinition {
+        name: "HyperionCity",
+        display_name: "Opportunity",
+    },
+    MapDefinition {
+        name: "HypInterlude",
+        display_name: "Friendship Gulag",
+    },
+    MapDefinition {
+        name: "Grass_Lynchwood",
+        display_name: "Lynchwood",
+    },
+    MapDefinition {
+        name: "BanditSlaughter",
+        display_name: "Fink's Slaughterhouse",
+    },
+    MapDefinition {
+        name: "Boss_Cliffs",
+        display_name: "The Bunker",
+    },
+    MapDefinition {
+        name: "VOGChamber",
+        display_name: "Control Core Angel",
+    },
+    MapDefinition {
+        name: "Sanctuary_Hole",
+        display_name: "Sanctuary Hole",
+    },
+    MapDefinition {
+        name: "Ash",
+        display_name: "Eridium Blight",
+    },
+    MapDefinition {
+        name: "CraterLake",
+        display_name: "Sawtooth Cauldron",
+    },
+    MapDefinition {
+        name: "Fyrestone",
+        display_name: "Arid Nexus - Boneyard",
+    },
+    MapDefinition {
+        name: "Stockade",
+        display_name: "Arid Nexus - Badlands",
+    },
+    MapDefinition {
+        name: "FinalBossAscent",
+        display_name: "Hero's Pass",
+    },
+    MapDefinition {
+        name: "Boss_Volcano",
+        display_name: "Vault of the Warrior",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        discovery_map, is_fully_explored, is_unlocked, lock_all_teleporters, lock_teleporter, map,
+        reveal_all_maps, reveal_map, set_fully_explored, set_spawn_teleporter, spawn_teleporter,
+        teleporter, unlock_all_teleporters, unlock_teleporter, TravelError,
+    };
+    use crate::load_test_save;
+
+    #[test]
+    fn teleporter_test() {
+        let mut save_game = load_test_save();
+        assert!(save_game
+            .VisitedTeleporters
+            .iter()
+            .all(|visited| teleporter(visited).is_some()));
+        assert_eq!(spawn_teleporter(&save_game), "IceEast");
+        assert!(matches!(
+            lock_teleporter(&mut save_game, "IceEast"),
+            Err(TravelError::SpawnTeleporter { .. })
+        ));
+
+        lock_teleporter(&mut save_game, "Lynchwood").unwrap();
+        assert!(!is_unlocked(&save_game, "Lynchwood"));
+        set_spawn_teleporter(&mut save_game, "Lynchwood").unwrap();
+        assert!(is_unlocked(&save_game, "Lynchwood"));
+        assert!(unlock_teleporter(&mut save_game, "Elpis").is_err());
+
+        lock_all_teleporters(&mut save_game);
+        assert_eq!(save_game.VisitedTeleporters, vec!["Lynchwood".to_string()]);
+        unlock_all_teleporters(&mut save_game);
+        assert_eq!(save_game.VisitedTeleporters.len(), 28);
+    }
+
+    #[test]
+    fn reveal_map_test() {
+        let mut save_game = load_test_save();
+        assert_eq!(discovery_map("GRASS_CLIFFS_PWDA_3"), Some("GRASS_CLIFFS"));
+        assert_eq!(discovery_map("FakeEntry"), None);
+        assert!(save_game.WorldDiscoveryList.iter().all(|discovery| {
+            discovery_map(discovery.DiscoveryName()).is_some_and(|name| map(name).is_some())
+        }));
+
+        assert_eq!(reveal_map(&mut save_game, "Grass").unwrap(), 16);
+        assert_eq!(reveal_map(&mut save_game, "GRASS").unwrap(), 0);
+        assert!(reveal_map(&mut save_game, "Elpis").is_err());
+        assert_eq!(reveal_all_maps(&mut save_game), 264);
+
+        assert!(is_fully_explored(&save_game, "DamTop"));
+        set_fully_explored(&mut save_game, "DamTop", false).unwrap();
+        assert!(!is_fully_explored(&save_game, "DamTop"));
+        set_fully_explored(&mut save_game, "Cove", true).unwrap();
+        assert_eq!(save_game.FullyExploredAreas.last().unwrap(), "Cove_P");
+    }
+}
@@ -0,0 +1,247 @@
+use borderlands2::equipment::GearIndex;
+use borderlands2::inventory::Overflow;
+use borderlands2::loadout;
+use borderlands2::lockout;
+use borderlands2::serial::time_seed;
+use borderlands2::transfer::{transfer, TransferMode, TransferOptions, TransferSource};
+use borderlands2::PlayerMark::PlayerMark;
+use borderlands2::WillowTwoPlayerSaveGame::WillowTwoPlayerSaveGame;
+
+const USAGE: &str = "Usage:
+    transfer <source.sav> <target.sav> <gear>... [--move] [--bank] [--seed <seed>] [--mark <mark>]
+    loadout list <save.sav>
+    loadout save <save.sav> <name>
+    loadout apply <save.sav> <name>
+    lockout list <save.sav>
+    lockout reset <save.sav> [<lockout>...]
+
+Gear is given as <list>:<index> with the lists w (weapons), pw (packed weapons),
+i (items), pi (packed items) and b (bank). Marks are trash, standard and favorite.
+Transferred serials are re-encrypted, --seed sets the seed of the first one.
+Loadouts are stored next to the save in <save.sav>.loadouts.
+Lockouts are listed with the play time left until they expire.
+Resetting lockouts without naming any resets all of them.
+Every changed save is first copied to <save.sav>.bak.";
+
+///
+/// Runs the command line interface and returns the exit code.
+///
+pub fn run(args: &[String]) -> i32 {
+    let result = match args.first().map(String::as_str) {
+        Some("transfer") => run_transfer(&args[1..]),
+        Some("loadout") => run_loadout(&args[1..]),
+        Some("lockout") => run_lockout(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            1
+        }
+    }
+}
+
+fn parse_gear(arg: &str) -> Result<TransferSource, String> {
+    let invalid = || format!("Invalid gear {}!\n\n{}", arg, USAGE);
+    let (list, index) = arg.split_once(':').ok_or_else(invalid)?;
+    let index = index.parse::<usize>().map_err(|_| invalid())?;
+    match list {
+        "w" => Ok(GearIndex::Weapon(index).into()),
+        "pw" => Ok(GearIndex::PackedWeapon(index).into()),
+        "i" => Ok(GearIndex::Item(index).into()),
+        "pi" => Ok(GearIndex::PackedItem(index).into()),
+        "b" => Ok(TransferSource::Bank(index)),
+        _ => Err(invalid()),
+    }
+}
+
+///
+/// Overwrites a save, keeping the previous file as <path>.bak.
+///
+/// The save is written to a temporary file first and renamed over the old one, so a failed
+/// write never leaves a truncated save behind.
+///
+fn replace_save(path: &str, save_game: &WillowTwoPlayerSaveGame) -> Result<(), String> {
+    let backup_path = format!("{}.bak", path);
+    let temp_path = format!("{}.tmp", path);
+    std::fs::copy(path, &backup_path)
+        .map_err(|err| format!("Could not back up {}: {}", path, err))?;
+    borderlands2::write_save(&temp_path, save_game).map_err(|err| err.to_string())?;
+    std::fs::rename(&temp_path, path).map_err(|err| format!("Could not replace {}: {}", path, err))
+}
+
+fn same_file(path: &str, other: &str) -> Result<bool, String> {
+    let canonical = |path: &str| {
+        std::fs::canonicalize(path).map_err(|err| format!("Could not open {}: {}", path, err))
+    };
+    Ok(canonical(path)? == canonical(other)?)
+}
+
+fn parse_mark(arg: &str) -> Result<PlayerMark, String> {
+    match arg {
+        "trash" => Ok(PlayerMark::Trash),
+        "standard" => Ok(PlayerMark::Standard),
+        "favorite" => Ok(PlayerMark::Favorite),
+        _ => Err(format!("Invalid mark {}!\n\n{}", arg, USAGE)),
+    }
+}
+
+fn run_transfer(args: &[String]) -> Result<(), String> {
+    let mut paths = Vec::new();
+    let mut gear = Vec::new();
+    let mut options = TransferOptions::default();
+    let mut bank = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--move" => options.mode = TransferMode::Move,
+            "--bank" => bank = true,
+            "--seed" => {
+                let seed = args.next().ok_or_else(|| USAGE.to_string())?;
+                options.seed = Some(
+                    seed.parse()
+                        .map_err(|_| format!("Invalid seed {}!", seed))?,
+                );
+            }
+            "--mark" => {
+                options.mark = Some(parse_mark(args.next().ok_or_else(|| USAGE.to_string())?)?)
+            }
+            _ if paths.len() < 2 => paths.push(arg.as_str()),
+            _ => gear.push(parse_gear(arg)?),
+        }
+    }
+    if paths.len() < 2 || gear.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    if bank {
+        // The transfer encrypts every serial with its own seed, this one is not used.
+        options.overflow = Overflow::Bank { seed: 0 };
+    }
+    if options.mode == TransferMode::Move && same_file(paths[0], paths[1])? {
+        // The target would be written first and then replaced by the source without the gear.
+        return Err(format!(
+            "Can not move gear from {} into the same save!",
+            paths[0]
+        ));
+    }
+
+    let mut source = borderlands2::load_save(paths[0]).map_err(|err| err.to_string())?;
+    let mut target = borderlands2::load_save(paths[1]).map_err(|err| err.to_string())?;
+    let report = transfer(&mut source, &mut target, &gear, &options);
+
+    for (gear, placement) in &report.transferred {
+        println!("Transferred {:?} to {:?}", gear, placement);
+    }
+    for (gear, err) in &report.rejected {
+        println!("Could not transfer {:?}: {}", gear, err);
+    }
+
+    if !report.transferred.is_empty() {
+        replace_save(paths[1], &target)?;
+        if options.mode == TransferMode::Move {
+            replace_save(paths[0], &source)?;
+        }
+    }
+    Ok(())
+}
+
+fn run_loadout(args: &[String]) -> Result<(), String> {
+    let (command, path) = match args {
+        [command, path, ..] => (command.as_str(), path.as_str()),
+        _ => return Err(USAGE.to_string()),
+    };
+    let sidecar_path = loadout::sidecar_path(path);
+    let mut loadouts = loadout::read_loadouts(&sidecar_path).map_err(|err| err.to_string())?;
+
+    match (command, args.get(2)) {
+        ("list", None) => {
+            for loadout in &loadouts {
+                println!(
+                    "{}: {} weapons, {} items",
+                    loadout.name,
+                    loadout.weapons.len(),
+                    loadout.items.len()
+                );
+            }
+            Ok(())
+        }
+        ("save", Some(name)) => {
+            let save_game = borderlands2::load_save(path).map_err(|err| err.to_string())?;
+            let snapshot = loadout::snapshot(&save_game, name).map_err(|err| err.to_string())?;
+            loadouts.retain(|loadout| loadout.name != *name);
+            loadouts.push(snapshot);
+            loadout::write_loadouts(&sidecar_path, &loadouts).map_err(|err| err.to_string())
+        }
+        ("apply", Some(name)) => {
+            let selected = loadouts
+                .iter()
+                .find(|loadout| loadout.name == *name)
+                .ok_or_else(|| format!("No loadout named {}!", name))?;
+            let mut save_game = borderlands2::load_save(path).map_err(|err| err.to_string())?;
+            loadout::apply(&mut save_game, selected, time_seed()).map_err(|err| err.to_string())?;
+            replace_save(path, &save_game)
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn run_lockout(args: &[String]) -> Result<(), String> {
+    let (command, path, names) = match args {
+        [command, path, names @ ..] => (command.as_str(), path.as_str(), names),
+        _ => return Err(USAGE.to_string()),
+    };
+    let mut save_game = borderlands2::load_save(path).map_err(|err| err.to_string())?;
+
+    match (command, names) {
+        ("list", []) => {
+            for (name, remaining) in lockout::lockouts(&save_game) {
+                println!(
+                    "{}: {}:{:02}:{:02} of play left",
+                    name,
+                    remaining / 3600,
+                    remaining / 60 % 60,
+                    remaining % 60
+                );
+            }
+            Ok(())
+        }
+        ("reset", []) => {
+            let count = lockout::reset_all_lockouts(&mut save_game);
+            println!("Reset {} lockouts", count);
+            replace_save(path, &save_game)
+        }
+        ("reset", names) => {
+            for name in names {
+                lockout::reset_lockout(&mut save_game, name).map_err(|err| err.to_string())?;
+            }
+            replace_save(path, &save_game)
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_transfer;
+
+    #[test]
+    fn move_to_same_save_test() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../borderlands2/resources/Save0001.sav"
+        );
+        let same = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../borderlands2/./resources/Save0001.sav"
+        );
+        let args: Vec<String> = [path, same, "pw:0", "--move"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let err = run_transfer(&args).unwrap_err();
+        assert!(err.contains("same save"));
+        assert!(!std::path::Path::new(&format!("{}.bak", path)).exists());
+    }
+}
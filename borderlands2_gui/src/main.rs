@@ -8,9 +8,15 @@ use iced::{Application, Command, Element, Length, Settings, Theme};
 
 use once_cell::sync::Lazy;
 
+mod cli;
+
 static SCROLLABLE_ID: Lazy<scrollable::Id> = Lazy::new(scrollable::Id::unique);
 
 pub fn main() -> iced::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
     ScrollableDemo::run(Settings::default())
 }
 